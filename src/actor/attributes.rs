@@ -0,0 +1,305 @@
+use std::time::Duration;
+
+use bevy::platform::collections::HashMap;
+
+use crate::actor::StatusEffectKind;
+use crate::items::PassiveItem;
+use crate::prelude::*;
+
+/// Every stat an actor has that buffs, debuffs, passives or biomes can modify. The
+/// single-field components ([`Speed`](super::Speed), [`Health::max`](super::Health),
+/// [`Armor`](super::Armor), ...) are written from the final values here and should not
+/// be changed directly.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    MaxHealth,
+    MoveSpeed,
+    ContactDamage,
+    Armor,
+    /// Health regained per second.
+    Regen,
+    PickupRadius,
+    DamageMultiplier,
+    /// Dashes that can be stored up, rounded down.
+    DashCharges,
+}
+
+impl Attribute {
+    pub const ALL: [Attribute; 8] = [
+        Attribute::MaxHealth,
+        Attribute::MoveSpeed,
+        Attribute::ContactDamage,
+        Attribute::Armor,
+        Attribute::Regen,
+        Attribute::PickupRadius,
+        Attribute::DamageMultiplier,
+        Attribute::DashCharges,
+    ];
+
+    pub fn default_base(&self) -> f32 {
+        match self {
+            Attribute::MaxHealth => 100.0,
+            Attribute::MoveSpeed => 128.0,
+            Attribute::ContactDamage => 1.0,
+            Attribute::Armor => 0.0,
+            Attribute::Regen => 0.0,
+            Attribute::PickupRadius => 96.0,
+            Attribute::DamageMultiplier => 1.0,
+            Attribute::DashCharges => 1.0,
+        }
+    }
+}
+
+/// Where a modifier came from, so everything a source granted can be taken back at once.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModifierSource {
+    Upgrade,
+    Passive(PassiveItem),
+    Status(StatusEffectKind),
+    Biome,
+    /// A buff or debuff applied by another entity, e.g. an aura.
+    Entity(Entity),
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModifierKind {
+    /// Added to the base value.
+    Flat,
+    /// Summed with every other percent-add modifier, then applied once.
+    PercentAdd,
+    /// Applied on its own, compounding with every other percent-multiply modifier.
+    PercentMultiply,
+}
+
+#[derive(Reflect, Debug, Clone)]
+pub struct AttributeModifier {
+    pub source: ModifierSource,
+    pub kind: ModifierKind,
+    pub value: f32,
+    /// Modifiers with a duration are dropped once it runs out, the rest stay until removed.
+    pub duration: Option<Timer>,
+}
+
+impl AttributeModifier {
+    pub fn flat(source: ModifierSource, value: f32) -> Self {
+        Self::new(source, ModifierKind::Flat, value)
+    }
+
+    pub fn percent_add(source: ModifierSource, value: f32) -> Self {
+        Self::new(source, ModifierKind::PercentAdd, value)
+    }
+
+    pub fn percent_multiply(source: ModifierSource, value: f32) -> Self {
+        Self::new(source, ModifierKind::PercentMultiply, value)
+    }
+
+    fn new(source: ModifierSource, kind: ModifierKind, value: f32) -> Self {
+        AttributeModifier {
+            source,
+            kind,
+            value,
+            duration: None,
+        }
+    }
+
+    pub fn with_duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(Timer::from_seconds(seconds, TimerMode::Once));
+        self
+    }
+}
+
+#[derive(Reflect, Debug, Clone)]
+struct AttributeStack {
+    base: f32,
+    modifiers: Vec<AttributeModifier>,
+    value: f32,
+}
+
+impl AttributeStack {
+    fn new(base: f32) -> Self {
+        AttributeStack {
+            base,
+            modifiers: Vec::new(),
+            value: base,
+        }
+    }
+
+    fn recompute(&mut self) {
+        let mut flat = 0.0;
+        let mut percent_add = 0.0;
+        let mut percent_multiply = 1.0;
+        for modifier in self.modifiers.iter() {
+            match modifier.kind {
+                ModifierKind::Flat => flat += modifier.value,
+                ModifierKind::PercentAdd => percent_add += modifier.value,
+                ModifierKind::PercentMultiply => percent_multiply *= 1.0 + modifier.value,
+            }
+        }
+
+        self.value = ((self.base + flat) * (1.0 + percent_add) * percent_multiply).max(0.0);
+    }
+}
+
+/// Base value and modifiers of every [`Attribute`]. Final values are cached and only
+/// recomputed when the base or modifiers of that attribute change.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct Attributes(HashMap<Attribute, AttributeStack>);
+
+impl Default for Attributes {
+    fn default() -> Self {
+        Attributes(
+            Attribute::ALL
+                .into_iter()
+                .map(|attribute| (attribute, AttributeStack::new(attribute.default_base())))
+                .collect(),
+        )
+    }
+}
+
+impl Attributes {
+    pub fn with_base(mut self, attribute: Attribute, base: f32) -> Self {
+        self.set_base(attribute, base);
+        self
+    }
+
+    fn stack_mut(&mut self, attribute: Attribute) -> &mut AttributeStack {
+        self.0
+            .entry(attribute)
+            .or_insert_with(|| AttributeStack::new(attribute.default_base()))
+    }
+
+    /// Final value with every modifier applied.
+    pub fn get(&self, attribute: Attribute) -> f32 {
+        self.0
+            .get(&attribute)
+            .map(|stack| stack.value)
+            .unwrap_or_else(|| attribute.default_base())
+    }
+
+    pub fn set_base(&mut self, attribute: Attribute, base: f32) {
+        let stack = self.stack_mut(attribute);
+        stack.base = base;
+        stack.recompute();
+    }
+
+    pub fn add_modifier(&mut self, attribute: Attribute, modifier: AttributeModifier) {
+        let stack = self.stack_mut(attribute);
+        stack.modifiers.push(modifier);
+        stack.recompute();
+    }
+
+    /// Removes the modifiers `source` granted to `attribute`.
+    pub fn remove_modifiers(&mut self, attribute: Attribute, source: ModifierSource) {
+        if let Some(stack) = self.0.get_mut(&attribute) {
+            let count = stack.modifiers.len();
+            stack.modifiers.retain(|modifier| modifier.source != source);
+            if stack.modifiers.len() != count {
+                stack.recompute();
+            }
+        }
+    }
+
+    /// Removes every modifier `source` granted, on any attribute.
+    pub fn remove_source(&mut self, source: ModifierSource) {
+        for attribute in Attribute::ALL {
+            self.remove_modifiers(attribute, source);
+        }
+    }
+
+    /// Advances timed modifiers and drops expired ones, returns whether any expired.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        let mut expired = false;
+        for stack in self.0.values_mut() {
+            let count = stack.modifiers.len();
+            stack.modifiers.retain_mut(|modifier| {
+                let Some(timer) = modifier.duration.as_mut() else {
+                    return true;
+                };
+                !timer.tick(delta).finished()
+            });
+            if stack.modifiers.len() != count {
+                stack.recompute();
+                expired = true;
+            }
+        }
+
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: ModifierSource = ModifierSource::Upgrade;
+
+    #[test]
+    fn recompute_applies_flat_then_percent_add_then_percent_multiply() {
+        let mut stack = AttributeStack::new(100.0);
+        stack.modifiers = vec![
+            AttributeModifier::flat(SOURCE, 20.0),
+            AttributeModifier::percent_add(SOURCE, 0.25),
+            AttributeModifier::percent_add(SOURCE, 0.25),
+            AttributeModifier::percent_multiply(SOURCE, 1.0),
+            AttributeModifier::percent_multiply(SOURCE, -0.5),
+        ];
+        stack.recompute();
+
+        // (100 + 20) * (1 + 0.25 + 0.25) * 2 * 0.5
+        assert_eq!(stack.value, 180.0);
+    }
+
+    #[test]
+    fn recompute_never_goes_below_zero() {
+        let mut stack = AttributeStack::new(10.0);
+        stack.modifiers = vec![AttributeModifier::flat(SOURCE, -50.0)];
+        stack.recompute();
+
+        assert_eq!(stack.value, 0.0);
+    }
+
+    #[test]
+    fn removing_a_source_restores_the_base_value() {
+        let passive = ModifierSource::Passive(PassiveItem::Magnet);
+        let mut attributes = Attributes::default().with_base(Attribute::PickupRadius, 100.0);
+        attributes.add_modifier(
+            Attribute::PickupRadius,
+            AttributeModifier::flat(passive, 20.0),
+        );
+        attributes.add_modifier(
+            Attribute::PickupRadius,
+            AttributeModifier::percent_add(passive, 0.5),
+        );
+        attributes.add_modifier(
+            Attribute::MoveSpeed,
+            AttributeModifier::percent_multiply(passive, 0.1),
+        );
+        assert_eq!(attributes.get(Attribute::PickupRadius), 180.0);
+
+        attributes.remove_source(passive);
+
+        assert_eq!(attributes.get(Attribute::PickupRadius), 100.0);
+        assert_eq!(
+            attributes.get(Attribute::MoveSpeed),
+            Attribute::MoveSpeed.default_base()
+        );
+    }
+
+    #[test]
+    fn timed_modifiers_expire_on_tick() {
+        let mut attributes = Attributes::default().with_base(Attribute::MoveSpeed, 100.0);
+        attributes.add_modifier(
+            Attribute::MoveSpeed,
+            AttributeModifier::percent_add(SOURCE, 0.5).with_duration(1.0),
+        );
+        attributes.add_modifier(Attribute::MoveSpeed, AttributeModifier::flat(SOURCE, 10.0));
+        assert_eq!(attributes.get(Attribute::MoveSpeed), 165.0);
+
+        assert!(!attributes.tick(Duration::from_millis(500)));
+        assert_eq!(attributes.get(Attribute::MoveSpeed), 165.0);
+
+        assert!(attributes.tick(Duration::from_millis(500)));
+        assert_eq!(attributes.get(Attribute::MoveSpeed), 110.0);
+    }
+}
@@ -0,0 +1,170 @@
+use bevy::platform::collections::HashMap;
+
+use crate::actor::*;
+
+/// Multiplier applied to the raw amount of a [`DamageEvent`] flagged as a crit.
+pub const CRIT_MULTIPLIER: f32 = 2.0;
+
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Fire,
+    Frost,
+    Poison,
+}
+
+/// A request to damage `target`. Nothing should write to [`Health`] directly,
+/// everything goes through this event so team filtering, mitigation and crits
+/// are applied in a single place.
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = ActorPlugin)]
+pub struct DamageEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub crit: bool,
+    /// Dealt by an effect ticking rather than a hit, so it never triggers on-hit effects.
+    pub over_time: bool,
+}
+
+impl DamageEvent {
+    pub fn new(source: Entity, target: Entity, amount: f32) -> Self {
+        DamageEvent {
+            source,
+            target,
+            amount,
+            damage_type: DamageType::Physical,
+            crit: false,
+            over_time: false,
+        }
+    }
+
+    pub fn with_type(mut self, damage_type: DamageType) -> Self {
+        self.damage_type = damage_type;
+        self
+    }
+
+    pub fn with_crit(mut self, crit: bool) -> Self {
+        self.crit = crit;
+        self
+    }
+
+    pub fn over_time(mut self) -> Self {
+        self.over_time = true;
+        self
+    }
+}
+
+/// Emitted once damage has been resolved and applied to the target's [`Health`].
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = ActorPlugin)]
+pub struct Damaged {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub crit: bool,
+    pub over_time: bool,
+}
+
+/// Emitted when resolved damage takes the target's [`Health`] to zero.
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = ActorPlugin)]
+pub struct Killed {
+    pub source: Entity,
+    pub target: Entity,
+}
+
+/// Flat reduction applied to every hit before resistances.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Armor(pub f32);
+
+/// Fraction of damage ignored per [`DamageType`], `1.0` being immune.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Resistances(pub HashMap<DamageType, f32>);
+
+impl Resistances {
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        self.0
+            .get(&damage_type)
+            .copied()
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// Final amount dealt by `event`, `damage_multiplier` being the attacker's
+/// [`Attribute::DamageMultiplier`].
+pub fn mitigate_damage(
+    event: &DamageEvent,
+    damage_multiplier: f32,
+    armor: Option<&Armor>,
+    resistances: Option<&Resistances>,
+) -> f32 {
+    let mut amount = event.amount * damage_multiplier;
+
+    if event.crit {
+        amount *= CRIT_MULTIPLIER;
+    }
+
+    if let Some(armor) = armor {
+        amount -= armor.0;
+    }
+
+    if let Some(resistances) = resistances {
+        amount *= 1.0 - resistances.get(event.damage_type);
+    }
+
+    amount.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fire_hit(amount: f32) -> DamageEvent {
+        DamageEvent::new(Entity::PLACEHOLDER, Entity::PLACEHOLDER, amount)
+            .with_type(DamageType::Fire)
+    }
+
+    fn half_fire_resistance() -> Resistances {
+        Resistances([(DamageType::Fire, 0.5)].into_iter().collect())
+    }
+
+    #[test]
+    fn mitigation_applies_crit_then_armor_then_resistance() {
+        let event = fire_hit(10.0).with_crit(true);
+
+        // (10 * 2 - 5) * 0.5, any other order gives 5
+        let amount = mitigate_damage(
+            &event,
+            1.0,
+            Some(&Armor(5.0)),
+            Some(&half_fire_resistance()),
+        );
+        assert_eq!(amount, 7.5);
+    }
+
+    #[test]
+    fn damage_multiplier_applies_before_armor() {
+        let amount = mitigate_damage(&fire_hit(10.0), 1.5, Some(&Armor(5.0)), None);
+        assert_eq!(amount, 10.0);
+    }
+
+    #[test]
+    fn mitigation_never_goes_below_zero() {
+        let event = fire_hit(10.0).with_crit(true);
+
+        let amount = mitigate_damage(
+            &event,
+            1.0,
+            Some(&Armor(100.0)),
+            Some(&half_fire_resistance()),
+        );
+        assert_eq!(amount, 0.0);
+    }
+}
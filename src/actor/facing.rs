@@ -0,0 +1,12 @@
+use crate::prelude::*;
+
+/// Last direction the actor moved in, always normalized.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Facing(pub Vec2);
+
+impl Default for Facing {
+    fn default() -> Self {
+        Facing(Vec2::X)
+    }
+}
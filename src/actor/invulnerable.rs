@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// Ignores incoming hits while active. Taking a hit grants `on_hit` seconds of it, damage
+/// over time never does and isn't blocked by it either.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Invulnerable {
+    pub on_hit: f32,
+    timer: Timer,
+}
+
+impl Invulnerable {
+    pub fn new(on_hit: f32) -> Self {
+        let mut timer = Timer::from_seconds(0.0, TimerMode::Once);
+        timer.tick(Duration::ZERO);
+        Invulnerable { on_hit, timer }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.timer.finished()
+    }
+
+    /// Seconds since invulnerability was last granted.
+    pub fn elapsed_secs(&self) -> f32 {
+        self.timer.elapsed_secs()
+    }
+
+    /// Makes the actor invulnerable for at least `seconds`, never shortening what's left.
+    pub fn grant(&mut self, seconds: f32) {
+        if seconds > self.timer.remaining_secs() {
+            self.timer = Timer::from_seconds(seconds, TimerMode::Once);
+        }
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.timer.tick(delta);
+    }
+}
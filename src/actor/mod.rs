@@ -8,12 +8,36 @@ pub(crate) use speed::*;
 mod health;
 pub(crate) use health::*;
 
+mod facing;
+pub(crate) use facing::*;
+
 mod team_flag;
 pub(crate) use team_flag::*;
 
+mod damage;
+pub(crate) use damage::*;
+
+mod attributes;
+pub(crate) use attributes::*;
+
+mod status_effects;
+pub(crate) use status_effects::*;
+
+mod invulnerable;
+pub(crate) use invulnerable::*;
+
 #[derive(Component, Reflect, Debug, Default)]
 #[reflect(Component)]
-#[require(Speed, Health, TeamFlag, GameEntity)]
+#[require(
+    Attributes,
+    StatusEffects,
+    Speed,
+    Health,
+    Armor,
+    TeamFlag,
+    Facing,
+    GameEntity
+)]
 pub struct Actor;
 
 #[butler_plugin]
@@ -0,0 +1,246 @@
+use serde::Deserialize;
+
+use crate::actor::*;
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum StatusEffectKind {
+    Burn,
+    Poison,
+    Slow,
+    Freeze,
+    Stun,
+}
+
+impl StatusEffectKind {
+    /// Ordered by which tint wins when several effects are active at once.
+    pub const ALL: [StatusEffectKind; 5] = [
+        StatusEffectKind::Freeze,
+        StatusEffectKind::Stun,
+        StatusEffectKind::Burn,
+        StatusEffectKind::Poison,
+        StatusEffectKind::Slow,
+    ];
+
+    pub fn definition(&self) -> StatusEffectDefinition {
+        match self {
+            StatusEffectKind::Burn => StatusEffectDefinition {
+                duration: 3.0,
+                tick_interval: 0.5,
+                damage_per_tick: 4.0,
+                damage_type: DamageType::Fire,
+                stack_rule: StackRule::Independent,
+                tint: Color::srgb(1.0, 0.55, 0.3),
+                ..default()
+            },
+            StatusEffectKind::Poison => StatusEffectDefinition {
+                duration: 5.0,
+                tick_interval: 1.0,
+                damage_per_tick: 2.0,
+                damage_type: DamageType::Poison,
+                stack_rule: StackRule::Stack { max: 5 },
+                tint: Color::srgb(0.5, 1.0, 0.4),
+                ..default()
+            },
+            StatusEffectKind::Slow => StatusEffectDefinition {
+                duration: 2.0,
+                slow: 0.4,
+                tint: Color::srgb(0.6, 0.75, 1.0),
+                ..default()
+            },
+            StatusEffectKind::Freeze => StatusEffectDefinition {
+                duration: 1.5,
+                slow: 1.0,
+                suppresses_ai: true,
+                tint: Color::srgb(0.4, 0.9, 1.0),
+                ..default()
+            },
+            StatusEffectKind::Stun => StatusEffectDefinition {
+                duration: 0.75,
+                suppresses_ai: true,
+                tint: Color::srgb(1.0, 1.0, 0.5),
+                ..default()
+            },
+        }
+    }
+}
+
+/// What happens when an effect is applied to an actor that already has it.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StackRule {
+    /// Keeps a single instance and restarts its duration.
+    #[default]
+    Refresh,
+    /// Keeps a single instance, adds a stack up to `max` and restarts its duration.
+    Stack { max: u32 },
+    /// Every application runs on its own.
+    Independent,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusEffectDefinition {
+    pub duration: f32,
+    /// Seconds between damage ticks, unused when `damage_per_tick` is zero.
+    pub tick_interval: f32,
+    /// Damage dealt per tick and per stack.
+    pub damage_per_tick: f32,
+    pub damage_type: DamageType,
+    pub stack_rule: StackRule,
+    /// Fraction of movement speed taken away per stack, `1.0` stops the actor entirely.
+    pub slow: f32,
+    /// Stops the actor from moving or thinking for as long as the effect lasts.
+    pub suppresses_ai: bool,
+    pub tint: Color,
+}
+
+impl Default for StatusEffectDefinition {
+    fn default() -> Self {
+        StatusEffectDefinition {
+            duration: 1.0,
+            tick_interval: 1.0,
+            damage_per_tick: 0.0,
+            damage_type: DamageType::Physical,
+            stack_rule: StackRule::Refresh,
+            slow: 0.0,
+            suppresses_ai: false,
+            tint: Color::WHITE,
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Clone)]
+pub struct ActiveStatusEffect {
+    pub kind: StatusEffectKind,
+    /// Whoever applied the effect, damage ticks are credited to them.
+    pub source: Entity,
+    pub stacks: u32,
+    pub remaining: Timer,
+    pub tick: Timer,
+}
+
+/// Effects currently running on an actor.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct StatusEffects(pub Vec<ActiveStatusEffect>);
+
+impl StatusEffects {
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.0.iter().any(|effect| effect.kind == kind)
+    }
+
+    /// Whether the actor is stunned, frozen or otherwise unable to move or act.
+    pub fn suppresses_ai(&self) -> bool {
+        self.0
+            .iter()
+            .any(|effect| effect.kind.definition().suppresses_ai)
+    }
+
+    /// Total slow per kind, used as the movement speed modifier for that kind.
+    pub fn slow(&self, kind: StatusEffectKind) -> f32 {
+        let slow = kind.definition().slow;
+        self.0
+            .iter()
+            .filter(|effect| effect.kind == kind)
+            .map(|effect| slow * effect.stacks as f32)
+            .sum::<f32>()
+            .min(1.0)
+    }
+
+    /// Tint of the highest priority active effect, `None` when nothing is active.
+    pub fn tint(&self) -> Option<Color> {
+        StatusEffectKind::ALL
+            .into_iter()
+            .find(|kind| self.has(*kind))
+            .map(|kind| kind.definition().tint)
+    }
+
+    pub fn apply(&mut self, kind: StatusEffectKind, source: Entity) {
+        let definition = kind.definition();
+        let existing = self.0.iter_mut().find(|effect| effect.kind == kind);
+
+        match (definition.stack_rule, existing) {
+            (StackRule::Refresh, Some(effect)) => {
+                effect.source = source;
+                effect.remaining.reset();
+            }
+            (StackRule::Stack { max }, Some(effect)) => {
+                effect.source = source;
+                effect.stacks = (effect.stacks + 1).min(max);
+                effect.remaining.reset();
+            }
+            _ => self.0.push(ActiveStatusEffect {
+                kind,
+                source,
+                stacks: 1,
+                remaining: Timer::from_seconds(definition.duration, TimerMode::Once),
+                tick: Timer::from_seconds(definition.tick_interval, TimerMode::Repeating),
+            }),
+        }
+    }
+}
+
+/// A request to put `kind` on `target`, goes through the same team filtering as damage.
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = ActorPlugin)]
+pub struct ApplyStatusEffect {
+    pub source: Entity,
+    pub target: Entity,
+    pub kind: StatusEffectKind,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn source(index: u32) -> Entity {
+        Entity::from_raw(index)
+    }
+
+    #[test]
+    fn refresh_keeps_one_instance_and_restarts_it() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffectKind::Slow, source(1));
+        effects.0[0].remaining.tick(Duration::from_secs(1));
+
+        effects.apply(StatusEffectKind::Slow, source(2));
+
+        assert_eq!(effects.0.len(), 1);
+        assert_eq!(effects.0[0].stacks, 1);
+        assert_eq!(effects.0[0].source, source(2));
+        assert_eq!(effects.0[0].remaining.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn stack_adds_stacks_up_to_max_and_restarts_it() {
+        let StackRule::Stack { max } = StatusEffectKind::Poison.definition().stack_rule else {
+            panic!("poison should stack");
+        };
+
+        let mut effects = StatusEffects::default();
+        for _ in 0..max + 2 {
+            if let Some(effect) = effects.0.first_mut() {
+                effect.remaining.tick(Duration::from_secs(1));
+            }
+            effects.apply(StatusEffectKind::Poison, source(1));
+        }
+
+        assert_eq!(effects.0.len(), 1);
+        assert_eq!(effects.0[0].stacks, max);
+        assert_eq!(effects.0[0].remaining.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn independent_runs_every_application_on_its_own() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffectKind::Burn, source(1));
+        effects.0[0].remaining.tick(Duration::from_secs(1));
+
+        effects.apply(StatusEffectKind::Burn, source(2));
+
+        assert_eq!(effects.0.len(), 2);
+        assert!(effects.0.iter().all(|effect| effect.stacks == 1));
+        assert_eq!(effects.0[0].remaining.elapsed(), Duration::from_secs(1));
+        assert_eq!(effects.0[1].remaining.elapsed(), Duration::ZERO);
+    }
+}
@@ -1,13 +1,14 @@
 use crate::actor::*;
+use crate::enemy::EnemyRank;
 
-#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(GameState::InGame))]
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
 fn add_health_bar_to_actor(query: Query<Entity, Added<Actor>>, mut commands: Commands) {
     for entity in query.iter() {
         HealthBar::add_to_entity(entity, &mut commands);
     }
 }
 
-// #[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(GameState::InGame))]
+// #[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
 fn update_health_bars(
     actors: Query<(&Health, &Children)>,
     mut health_bar_query: Query<&mut Sprite, With<HealthBar>>,
@@ -24,3 +25,313 @@ fn update_health_bars(
         }
     }
 }
+
+/// Walks up the hierarchy from `entity` until something with a [`TeamFlag`] is found,
+/// so weapons and their satellites fight for whoever is holding them.
+fn team_of(entity: Entity, teams: &Query<&TeamFlag>, parents: &Query<&ChildOf>) -> Team {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if let Ok(flag) = teams.get(e) {
+            return flag.0;
+        }
+
+        current = parents.get(e).ok().map(|child_of| child_of.parent());
+    }
+
+    Team::Neutral
+}
+
+/// Damage multiplier of whoever is ultimately dealing damage as `entity`, found the same
+/// way as [`team_of`].
+fn damage_multiplier_of(
+    entity: Entity,
+    attributes: &Query<&Attributes>,
+    parents: &Query<&ChildOf>,
+) -> f32 {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if let Ok(attributes) = attributes.get(e) {
+            return attributes.get(Attribute::DamageMultiplier);
+        }
+
+        current = parents.get(e).ok().map(|child_of| child_of.parent());
+    }
+
+    1.0
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
+fn resolve_damage(
+    mut events: EventReader<DamageEvent>,
+    mut target_query: Query<(
+        &mut Health,
+        Option<&Armor>,
+        Option<&Resistances>,
+        Option<&mut Invulnerable>,
+    )>,
+    teams: Query<&TeamFlag>,
+    attributes: Query<&Attributes>,
+    parents: Query<&ChildOf>,
+    mut damaged: EventWriter<Damaged>,
+    mut killed: EventWriter<Killed>,
+) {
+    for event in events.read() {
+        let Ok((mut health, armor, resistances, mut invulnerable)) =
+            target_query.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        // Already dead, waiting to be cleaned up
+        if health.current <= 0.0 {
+            continue;
+        }
+
+        // Over time damage lands every frame, i-frames would cut a drain off after one tick
+        if !event.over_time && invulnerable.as_ref().is_some_and(|i| i.is_active()) {
+            continue;
+        }
+
+        let source_team = team_of(event.source, &teams, &parents);
+        let target_team = team_of(event.target, &teams, &parents);
+        if !source_team.is_hostile_to(target_team) {
+            continue;
+        }
+
+        let damage_multiplier = damage_multiplier_of(event.source, &attributes, &parents);
+        let amount = mitigate_damage(event, damage_multiplier, armor, resistances);
+        if amount <= 0.0 {
+            continue;
+        }
+
+        health.current -= amount;
+
+        // Started here rather than on `Damaged` so a crowd can't land several hits in one frame
+        if let Some(invulnerable) = invulnerable.as_mut().filter(|_| !event.over_time) {
+            let on_hit = invulnerable.on_hit;
+            invulnerable.grant(on_hit);
+        }
+
+        damaged.write(Damaged {
+            source: event.source,
+            target: event.target,
+            amount,
+            damage_type: event.damage_type,
+            crit: event.crit,
+            over_time: event.over_time,
+        });
+
+        if health.current <= 0.0 {
+            killed.write(Killed {
+                source: event.source,
+                target: event.target,
+            });
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
+fn tick_attribute_modifiers(time: Res<Time>, mut query: Query<&mut Attributes>) {
+    for mut attributes in query.iter_mut() {
+        // Ticking alone shouldn't count as a change, only modifiers running out should
+        if attributes.bypass_change_detection().tick(time.delta()) {
+            attributes.set_changed();
+        }
+    }
+}
+
+/// Writes final attribute values into the components the rest of the game reads.
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing), after = tick_attribute_modifiers)]
+fn apply_actor_attributes(
+    mut query: Query<(&Attributes, &mut Speed, &mut Health, &mut Armor), Changed<Attributes>>,
+) {
+    for (attributes, mut speed, mut health, mut armor) in query.iter_mut() {
+        speed.0 = attributes.get(Attribute::MoveSpeed);
+        armor.0 = attributes.get(Attribute::Armor);
+
+        let max = attributes.get(Attribute::MaxHealth);
+        if max != health.max {
+            // Growing max health heals by the same amount, shrinking it only clamps
+            let gained = (max - health.max).max(0.0);
+            health.max = max;
+            health.current = (health.current + gained).min(max);
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
+fn regenerate_health(time: Res<Time>, mut query: Query<(&Attributes, &mut Health)>) {
+    for (attributes, mut health) in query.iter_mut() {
+        let regen = attributes.get(Attribute::Regen);
+        if regen <= 0.0 || health.current <= 0.0 || health.current >= health.max {
+            continue;
+        }
+
+        health.current = (health.current + regen * time.delta_secs()).min(health.max);
+    }
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
+fn apply_status_effects(
+    mut events: EventReader<ApplyStatusEffect>,
+    mut target_query: Query<(&mut StatusEffects, &Health)>,
+    teams: Query<&TeamFlag>,
+    parents: Query<&ChildOf>,
+) {
+    for event in events.read() {
+        let Ok((mut effects, health)) = target_query.get_mut(event.target) else {
+            continue;
+        };
+
+        if health.current <= 0.0 {
+            continue;
+        }
+
+        let source_team = team_of(event.source, &teams, &parents);
+        let target_team = team_of(event.target, &teams, &parents);
+        if !source_team.is_hostile_to(target_team) {
+            continue;
+        }
+
+        effects.apply(event.kind, event.source);
+    }
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing), after = apply_status_effects)]
+fn tick_status_effects(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut StatusEffects)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, mut effects) in query.iter_mut() {
+        if effects.0.is_empty() {
+            continue;
+        }
+
+        // Timers tick every frame, only effects running out count as a change
+        let active = effects.bypass_change_detection();
+        for effect in active.0.iter_mut() {
+            let definition = effect.kind.definition();
+            effect.remaining.tick(time.delta());
+
+            if definition.damage_per_tick > 0.0 {
+                let ticks = effect.tick.tick(time.delta()).times_finished_this_tick();
+                if ticks > 0 {
+                    damage_events.write(
+                        DamageEvent::new(
+                            effect.source,
+                            entity,
+                            definition.damage_per_tick * (effect.stacks * ticks) as f32,
+                        )
+                        .with_type(definition.damage_type)
+                        .over_time(),
+                    );
+                }
+            }
+        }
+
+        let count = active.0.len();
+        active.0.retain(|effect| !effect.remaining.finished());
+        if active.0.len() != count {
+            effects.set_changed();
+        }
+    }
+}
+
+/// Turns slows into movement speed modifiers so they stack with everything else.
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing), after = tick_status_effects)]
+fn apply_status_effect_modifiers(
+    mut query: Query<(&StatusEffects, &mut Attributes), Changed<StatusEffects>>,
+) {
+    for (effects, mut attributes) in query.iter_mut() {
+        for kind in StatusEffectKind::ALL {
+            let source = ModifierSource::Status(kind);
+            attributes.remove_modifiers(Attribute::MoveSpeed, source);
+
+            let slow = effects.slow(kind);
+            if slow > 0.0 {
+                attributes.add_modifier(
+                    Attribute::MoveSpeed,
+                    AttributeModifier::percent_multiply(source, -slow),
+                );
+            }
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing), after = tick_status_effects)]
+fn tint_status_effects(
+    mut query: Query<(&StatusEffects, &mut Sprite, Option<&EnemyRank>), Changed<StatusEffects>>,
+) {
+    for (effects, mut sprite, rank) in query.iter_mut() {
+        // Only the colour changes, whatever is fading the sprite keeps control of alpha
+        let tint = effects
+            .tint()
+            .unwrap_or_else(|| rank.map_or(Color::WHITE, EnemyRank::tint));
+        sprite.color = tint.with_alpha(sprite.color.alpha());
+    }
+}
+
+#[add_system(schedule = Update, plugin = ActorPlugin, run_if = in_state(InGameState::Playing))]
+fn tick_invulnerability(time: Res<Time>, mut query: Query<&mut Invulnerable>) {
+    for mut invulnerable in query.iter_mut() {
+        if invulnerable.is_active() {
+            invulnerable.tick(time.delta());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::ContactDamageModel;
+
+    fn damage_app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<DamageEvent>()
+            .add_event::<Damaged>()
+            .add_event::<Killed>()
+            .add_systems(Update, resolve_damage);
+
+        let enemy = app.world_mut().spawn(TeamFlag(Team::Enemy)).id();
+        let mut invulnerable = Invulnerable::new(1.0);
+        invulnerable.grant(1.0);
+        let player = app
+            .world_mut()
+            .spawn((TeamFlag(Team::Player), Health::default(), invulnerable))
+            .id();
+
+        (app, enemy, player)
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Health>(entity).unwrap().current
+    }
+
+    #[test]
+    fn i_frames_block_hits() {
+        let (mut app, enemy, player) = damage_app();
+
+        let event = ContactDamageModel::PerHit.damage_event(enemy, player, 10.0, 0.5);
+        app.world_mut().send_event(event);
+        app.update();
+
+        assert_eq!(health(&app, player), 100.0);
+    }
+
+    #[test]
+    fn damage_over_time_lands_through_i_frames() {
+        let (mut app, enemy, player) = damage_app();
+
+        let event = ContactDamageModel::PerSecond.damage_event(enemy, player, 10.0, 0.5);
+        app.world_mut().send_event(event);
+        app.update();
+
+        assert_eq!(health(&app, player), 95.0);
+        // And never extends them
+        let invulnerable = app.world().get::<Invulnerable>(player).unwrap();
+        assert!(invulnerable.is_active());
+        assert!(invulnerable.elapsed_secs() < 1.0);
+    }
+}
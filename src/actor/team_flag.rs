@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-#[derive(Reflect, Debug, Default)]
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Team {
     #[default]
     Neutral,
@@ -8,6 +8,13 @@ pub enum Team {
     Enemy,
 }
 
+impl Team {
+    /// Neutral damages and can be damaged by everyone, other teams only hurt each other.
+    pub fn is_hostile_to(&self, other: Team) -> bool {
+        *self == Team::Neutral || other == Team::Neutral || *self != other
+    }
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct TeamFlag(pub Team);
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use crate::camera::*;
+use crate::settings::Settings;
+
+/// Adds trauma to the main camera's [`CameraShake`], `0.0..=1.0`.
+#[derive(Event, Debug, Clone, Copy)]
+#[add_event(plugin = CameraPlugin)]
+pub struct ShakeCamera(pub f32);
+
+/// Slows the whole game down to `time_scale` for `duration` real seconds to sell a big hit.
+#[derive(Event, Debug, Clone, Copy)]
+#[add_event(plugin = CameraPlugin)]
+pub struct HitStop {
+    pub duration: f32,
+    pub time_scale: f32,
+}
+
+/// Tints the whole screen with `color`, fading out over `duration` seconds.
+#[derive(Event, Debug, Clone, Copy)]
+#[add_event(plugin = CameraPlugin)]
+pub struct FlashScreen {
+    pub color: Color,
+    pub duration: f32,
+}
+
+/// Real seconds left on the current hit-stop.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+#[insert_resource(plugin = CameraPlugin)]
+struct HitStopRemaining(f32);
+
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+struct ScreenFlashOverlay {
+    color: Color,
+    /// Strength at the start of the flash, already scaled by the accessibility setting.
+    strength: f32,
+    timer: Timer,
+}
+
+#[add_system(schedule = Startup, plugin = CameraPlugin)]
+fn spawn_screen_flash(mut commands: Commands) {
+    let mut timer = Timer::from_seconds(0.0, TimerMode::Once);
+    timer.tick(Duration::ZERO);
+
+    commands.spawn((
+        ScreenFlashOverlay { timer, ..default() },
+        Name::new("Screen Flash"),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        // Over the HUD, under menus
+        GlobalZIndex(5),
+        Pickable::IGNORE,
+    ));
+}
+
+#[add_system(schedule = Update, plugin = CameraPlugin)]
+fn handle_shake_events(
+    mut events: EventReader<ShakeCamera>,
+    settings: Res<Settings>,
+    mut camera_query: Query<&mut CameraShake, With<MainCamera>>,
+) {
+    for event in events.read() {
+        for mut shake in camera_query.iter_mut() {
+            shake.add_trauma(event.0 * settings.effects_scale);
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = CameraPlugin)]
+fn handle_hit_stop_events(
+    mut events: EventReader<HitStop>,
+    settings: Res<Settings>,
+    mut remaining: ResMut<HitStopRemaining>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    for event in events.read() {
+        let duration = event.duration * settings.effects_scale;
+        if duration <= 0.0 {
+            continue;
+        }
+
+        remaining.0 = remaining.0.max(duration);
+        time.set_relative_speed(event.time_scale.min(time.relative_speed()));
+    }
+}
+
+#[add_system(schedule = Update, plugin = CameraPlugin, after = handle_hit_stop_events)]
+fn tick_hit_stop(
+    real_time: Res<Time<Real>>,
+    mut remaining: ResMut<HitStopRemaining>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if remaining.0 <= 0.0 {
+        return;
+    }
+
+    // Real time so the dip lasts as long as intended however slow the game runs meanwhile
+    remaining.0 -= real_time.delta_secs();
+    if remaining.0 <= 0.0 {
+        remaining.0 = 0.0;
+        time.set_relative_speed(1.0);
+    }
+}
+
+/// Leaving a run mid hit-stop would otherwise leave the next one in slow motion.
+#[add_system(schedule = OnExit(GameState::InGame), plugin = CameraPlugin)]
+fn reset_hit_stop(mut remaining: ResMut<HitStopRemaining>, mut time: ResMut<Time<Virtual>>) {
+    remaining.0 = 0.0;
+    time.set_relative_speed(1.0);
+}
+
+#[add_system(schedule = Update, plugin = CameraPlugin)]
+fn handle_flash_events(
+    mut events: EventReader<FlashScreen>,
+    settings: Res<Settings>,
+    mut overlay_query: Query<&mut ScreenFlashOverlay>,
+) {
+    // Only the latest flash is shown
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    if settings.effects_scale <= 0.0 {
+        return;
+    }
+
+    for mut overlay in overlay_query.iter_mut() {
+        overlay.color = event.color;
+        overlay.strength = event.color.alpha() * settings.effects_scale;
+        overlay.timer = Timer::from_seconds(event.duration, TimerMode::Once);
+    }
+}
+
+#[add_system(schedule = Update, plugin = CameraPlugin, after = handle_flash_events)]
+fn fade_screen_flash(
+    real_time: Res<Time<Real>>,
+    mut overlay_query: Query<(&mut ScreenFlashOverlay, &mut BackgroundColor)>,
+) {
+    for (mut overlay, mut background) in overlay_query.iter_mut() {
+        if overlay.timer.finished() {
+            background.set_if_neq(BackgroundColor(Color::NONE));
+            continue;
+        }
+
+        overlay.timer.tick(real_time.delta());
+        let alpha = overlay.strength * overlay.timer.fraction_remaining();
+        background.0 = overlay.color.with_alpha(alpha);
+    }
+}
@@ -2,12 +2,68 @@ use crate::prelude::*;
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
-#[require(Camera2d, Name::new("Main Camera"))]
+#[require(Camera2d, CameraShake, CameraFollow, Name::new("Main Camera"))]
 pub struct MainCamera {
     pub base_speed: f32,
     pub max_speed: f32,
 }
 
+/// How the camera decides where to look, cycled with a hotkey.
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+#[insert_resource(plugin = CameraPlugin)]
+pub enum CameraMode {
+    /// Frames every [`CameraFocus`], zooming out to keep them all on screen.
+    #[default]
+    Follow,
+    /// WASD panning and wheel zoom, dev builds only. Player input is ignored meanwhile.
+    Free,
+    /// Stays wherever it was.
+    Locked,
+}
+
+impl CameraMode {
+    pub fn next(&self) -> Self {
+        match self {
+            CameraMode::Follow if cfg!(feature = "dev") => CameraMode::Free,
+            CameraMode::Follow | CameraMode::Free => CameraMode::Locked,
+            CameraMode::Locked => CameraMode::Follow,
+        }
+    }
+}
+
+/// Half size of the box around the camera centre the focus can move in freely.
+const DEAD_ZONE: Vec2 = Vec2::new(96.0, 64.0);
+/// How far ahead of the focus the camera leads while it's moving.
+const LOOK_AHEAD_DISTANCE: f32 = 160.0;
+const LOOK_AHEAD_MIN_SPEED: f32 = 16.0;
+const LOOK_AHEAD_SMOOTHING: f32 = 3.0;
+const ZOOM_SMOOTHING: f32 = 2.0;
+
+/// Follow mode state carried between frames.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct CameraFollow {
+    last_focus: Option<Vec2>,
+    look_ahead: Vec2,
+}
+
+/// Trauma based shake, the offset grows with the square of `trauma` so small knocks
+/// barely register and big ones really shake.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct CameraShake {
+    pub trauma: f32,
+    /// Offset applied last frame, taken back off before the next one is added.
+    offset: Vec2,
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 #[require(Transform, GlobalTransform, Name::new("Camera Focus"))]
@@ -19,8 +75,17 @@ impl Default for CameraFocus {
     }
 }
 
+/// Keys the free camera pans with, anything else bound to them moves both at once.
+pub const PAN_UP_KEYS: [KeyCode; 2] = [KeyCode::KeyW, KeyCode::ArrowUp];
+pub const PAN_DOWN_KEYS: [KeyCode; 2] = [KeyCode::KeyS, KeyCode::ArrowDown];
+pub const PAN_LEFT_KEYS: [KeyCode; 2] = [KeyCode::KeyA, KeyCode::ArrowLeft];
+pub const PAN_RIGHT_KEYS: [KeyCode; 2] = [KeyCode::KeyD, KeyCode::ArrowRight];
+
 #[butler_plugin]
 #[add_plugin(to_group = RenderingPlugins)]
 struct CameraPlugin;
 
+mod effects;
+pub(crate) use effects::*;
+
 mod systems;
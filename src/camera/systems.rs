@@ -1,8 +1,10 @@
 use bevy::render::camera::ScalingMode;
+use rand::Rng;
 
 use crate::camera::*;
+use crate::input::MenuAction;
 
-#[add_system(schedule = OnEnter(GameState::InGame), plugin = CameraPlugin)]
+#[add_system(schedule = Startup, plugin = CameraPlugin)]
 fn spawn_camera(mut commands: Commands) {
     info!("Spawning main camera");
     commands.spawn((
@@ -19,13 +21,57 @@ fn spawn_camera(mut commands: Commands) {
     ));
 }
 
-// #[add_system(schedule = Update, plugin = CameraPlugin, run_if = in_state(GameState::InGame))]
+#[add_system(schedule = OnEnter(GameState::InGame), plugin = CameraPlugin)]
+fn reset_camera(
+    mut camera_query: Query<(&mut Transform, &mut Projection, &mut CameraFollow), With<MainCamera>>,
+    mut mode: ResMut<CameraMode>,
+) {
+    *mode = CameraMode::Follow;
+    for (mut transform, mut projection, mut follow) in camera_query.iter_mut() {
+        transform.translation = Vec3::ZERO;
+        *follow = CameraFollow::default();
+        if let Projection::Orthographic(ref mut ortho) = *projection {
+            ortho.scale = 1.0;
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = CameraPlugin, run_if = in_state(GameState::InGame))]
+fn cycle_camera_mode(menu_actions: Res<ActionState<MenuAction>>, mut mode: ResMut<CameraMode>) {
+    if menu_actions.just_pressed(&MenuAction::CycleCamera) {
+        *mode = mode.next();
+        info!("Camera mode: {:?}", *mode);
+    }
+}
+
+/// Lerp factor that closes the same fraction of the gap per second at any frame rate.
+fn smoothing(rate: f32, delta_secs: f32) -> f32 {
+    1.0 - (-rate * delta_secs).exp()
+}
+
+#[add_system(
+    schedule = Update,
+    plugin = CameraPlugin,
+    run_if = in_state(GameState::InGame),
+    run_if = resource_equals(CameraMode::Follow),
+    after = cycle_camera_mode
+)]
 fn update_camera(
-    mut camera_query: Query<(&mut Transform, &mut Projection, &MainCamera), Without<CameraFocus>>,
+    mut camera_query: Query<
+        (
+            &mut Transform,
+            &mut Projection,
+            &MainCamera,
+            &mut CameraFollow,
+        ),
+        Without<CameraFocus>,
+    >,
     focus_query: Query<(&Transform, &CameraFocus), With<CameraFocus>>,
     time: Res<Time>,
 ) {
-    if let Ok((mut camera_transform, mut projection, camera)) = camera_query.single_mut() {
+    if let Ok((mut camera_transform, mut projection, camera, mut follow)) =
+        camera_query.single_mut()
+    {
         // Collect all active foci
         let mut sum = Vec3::ZERO;
         let mut count = 0;
@@ -50,24 +96,54 @@ fn update_camera(
         }
 
         if count == 0 {
+            follow.last_focus = None;
             return;
         }
 
-        let avg_pos = sum / count as f32;
+        let avg_pos = (sum / count as f32).truncate();
+        let delta_secs = time.delta_secs();
 
-        let target = Vec3::new(avg_pos.x, avg_pos.y, camera_transform.translation.z);
-        let current = camera_transform.translation;
-        let distance = current.distance(target);
+        // Lead the camera in the direction the group is heading
+        let focus_velocity = match follow.last_focus {
+            Some(last) if delta_secs > 0.0 => (avg_pos - last) / delta_secs,
+            _ => Vec2::ZERO,
+        };
+        follow.last_focus = Some(avg_pos);
+        let desired_look_ahead = if focus_velocity.length() > LOOK_AHEAD_MIN_SPEED {
+            focus_velocity.normalize() * LOOK_AHEAD_DISTANCE
+        } else {
+            Vec2::ZERO
+        };
+        follow.look_ahead = follow.look_ahead.lerp(
+            desired_look_ahead,
+            smoothing(LOOK_AHEAD_SMOOTHING, delta_secs),
+        );
+
+        // Only chase the target once it leaves the dead-zone, and only as far as its edge
+        let current = camera_transform.translation.truncate();
+        let target = avg_pos + follow.look_ahead;
+        let offset = target - current;
+        let outside = Vec2::new(
+            offset.x - offset.x.clamp(-DEAD_ZONE.x, DEAD_ZONE.x),
+            offset.y - offset.y.clamp(-DEAD_ZONE.y, DEAD_ZONE.y),
+        );
 
-        let speed = camera
-            .base_speed
-            .max((distance / 10.0) * camera.max_speed)
-            .min(camera.max_speed);
+        if outside != Vec2::ZERO {
+            let distance = outside.length();
+            let speed = camera
+                .base_speed
+                .max((distance / 10.0) * camera.max_speed)
+                .min(camera.max_speed);
 
-        camera_transform.translation =
-            current.lerp(target, 1.0 - (-speed * time.delta_secs()).exp());
+            let next = current + outside * smoothing(speed, delta_secs);
+            camera_transform.translation.x = next.x;
+            camera_transform.translation.y = next.y;
+        }
 
         if let Projection::Orthographic(ref mut ortho) = *projection {
+            // Undo any free camera zoom
+            ortho.scale += (1.0 - ortho.scale) * smoothing(ZOOM_SMOOTHING, delta_secs);
+
             if let ScalingMode::FixedVertical {
                 ref mut viewport_height,
             } = ortho.scaling_mode
@@ -91,7 +167,8 @@ fn update_camera(
                 new_height = new_height.max(desired_height);
                 new_height = new_height.max(2160.0);
 
-                *viewport_height = new_height;
+                *viewport_height +=
+                    (new_height - *viewport_height) * smoothing(ZOOM_SMOOTHING, delta_secs);
             }
         }
     }
@@ -115,7 +192,10 @@ const MAX_SCALE: f32 = 50.0;
 #[add_system(
     schedule = Update,
     plugin = CameraPlugin,
-    run_if = in_state(GameState::InGame)
+    run_if = in_state(GameState::InGame),
+    run_if = resource_equals(CameraMode::Free),
+    after = cycle_camera_mode,
+    before = update_camera
 )]
 fn camera_pan_zoom(
     time: Res<Time>,
@@ -129,16 +209,16 @@ fn camera_pan_zoom(
 
     // --------- PAN (WASD) ----------
     let mut dir = Vec2::ZERO;
-    if keys.pressed(KeyCode::KeyW) || keys.pressed(KeyCode::ArrowUp) {
+    if keys.any_pressed(PAN_UP_KEYS) {
         dir.y += 1.0;
     }
-    if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
+    if keys.any_pressed(PAN_DOWN_KEYS) {
         dir.y -= 1.0;
     }
-    if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
+    if keys.any_pressed(PAN_RIGHT_KEYS) {
         dir.x += 1.0;
     }
-    if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
+    if keys.any_pressed(PAN_LEFT_KEYS) {
         dir.x -= 1.0;
     }
 
@@ -195,3 +275,29 @@ fn camera_pan_zoom(
         ortho.scale = (ortho.scale * zoom_mul).clamp(MIN_SCALE, MAX_SCALE);
     }
 }
+
+/// World units the camera moves at full trauma, the viewport is 2160 units tall.
+const MAX_SHAKE_OFFSET: f32 = 48.0;
+/// Trauma lost per second.
+const SHAKE_DECAY: f32 = 1.5;
+
+#[add_system(schedule = Update, plugin = CameraPlugin, run_if = in_state(InGameState::Playing), after = update_camera)]
+fn apply_camera_shake(
+    time: Res<Time>,
+    mut camera_query: Query<(&mut Transform, &mut CameraShake), With<MainCamera>>,
+) {
+    for (mut transform, mut shake) in camera_query.iter_mut() {
+        if shake.trauma <= 0.0 && shake.offset == Vec2::ZERO {
+            continue;
+        }
+
+        transform.translation -= shake.offset.extend(0.0);
+
+        let mut rng = rand::thread_rng();
+        let strength = shake.trauma * shake.trauma * MAX_SHAKE_OFFSET;
+        shake.offset = Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0)) * strength;
+        shake.trauma = (shake.trauma - SHAKE_DECAY * time.delta_secs()).max(0.0);
+
+        transform.translation += shake.offset.extend(0.0);
+    }
+}
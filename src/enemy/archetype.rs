@@ -0,0 +1,101 @@
+use bevy::platform::collections::HashMap;
+
+use crate::actor::*;
+use crate::animated_sprite::*;
+use crate::enemy::*;
+
+/// Half the width of an enemy's hitbox, anything testing against enemy centres pads by it.
+pub const ENEMY_HALF_EXTENT: f32 = 16.0;
+
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub enum EnemyArchetype {
+    #[default]
+    Slime,
+}
+
+impl EnemyArchetype {
+    /// Experience dropped on death.
+    pub fn experience(&self) -> f32 {
+        match self {
+            EnemyArchetype::Slime => 1.0,
+        }
+    }
+
+    /// Damage dealt per hit on touch, see [`ContactDamageModel`].
+    pub fn contact_damage(&self) -> f32 {
+        match self {
+            EnemyArchetype::Slime => 10.0,
+        }
+    }
+
+    /// Fraction of incoming knockback ignored, `1.0` being immovable.
+    pub fn knockback_resistance(&self) -> f32 {
+        match self {
+            EnemyArchetype::Slime => 0.0,
+        }
+    }
+
+    /// Everything needed to spawn an enemy of this archetype, scoped to the current run.
+    pub fn bundle(&self, rank: EnemyRank, assets: &SlimeAssets, position: Vec3) -> impl Bundle {
+        let multiplier = rank.stat_multiplier();
+
+        match self {
+            EnemyArchetype::Slime => (
+                Enemy,
+                *self,
+                rank,
+                Name::new("Enemy"),
+                StateScoped(GameState::InGame),
+                TeamFlag(Team::Enemy),
+                Sprite {
+                    image: assets.sprite.clone(),
+                    color: rank.tint(),
+                    custom_size: Some(Vec2::new(32.0, 32.0)),
+                    texture_atlas: Some(TextureAtlas {
+                        layout: assets.layout.clone(),
+                        index: 0,
+                    }),
+                    ..default()
+                },
+                AnimatedSprite {
+                    animations: {
+                        let mut map = HashMap::new();
+                        map.insert(
+                            "move".to_string(),
+                            Animation {
+                                frames: (0..5).collect(),
+                                durations: vec![0.1; 6],
+                            },
+                        );
+                        map
+                    },
+                    current_animation: "move".to_string(),
+                    current_frame: 0,
+                    timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+                },
+                Attributes::default()
+                    .with_base(Attribute::MoveSpeed, 64.0)
+                    .with_base(
+                        Attribute::MaxHealth,
+                        Attribute::MaxHealth.default_base() * multiplier,
+                    )
+                    .with_base(Attribute::ContactDamage, self.contact_damage() * multiplier),
+                Aabb::from_min_max(
+                    Vec3::new(-ENEMY_HALF_EXTENT, -ENEMY_HALF_EXTENT, 0.0),
+                    Vec3::new(ENEMY_HALF_EXTENT, ENEMY_HALF_EXTENT, 0.0),
+                ),
+                Transform::from_translation(position),
+            ),
+        }
+    }
+}
+
+/// Emitted right before a dead enemy is despawned.
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = EnemyPlugin)]
+pub struct EnemyDied {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub archetype: EnemyArchetype,
+}
@@ -23,6 +23,35 @@ impl KdPoint for Collision {
     }
 }
 
+fn distance_squared_to_segment(point: Vec2, from: Vec2, to: Vec2) -> f32 {
+    let segment = to - from;
+    let length_squared = segment.length_squared();
+    if length_squared <= f32::EPSILON {
+        return point.distance_squared(from);
+    }
+
+    let t = ((point - from).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance_squared(from + segment * t)
+}
+
+impl EnemyKdTree {
+    /// Enemies within `radius` of the segment from `from` to `to`. Anything that can move
+    /// further than its own size in a frame should sweep with this rather than testing
+    /// where it ended up, otherwise it skips over enemies at low frame rates.
+    pub fn within_capsule(&self, from: Vec2, to: Vec2, radius: f32) -> Vec<&Collision> {
+        let center = (from + to) / 2.0;
+        let reach = from.distance(to) / 2.0 + radius;
+
+        self.0
+            .within_radius(&[center.x, center.y], reach)
+            .into_iter()
+            .filter(|collision| {
+                distance_squared_to_segment(collision.pos, from, to) <= radius * radius
+            })
+            .collect()
+    }
+}
+
 impl Default for EnemyKdTree {
     fn default() -> Self {
         Self(KdTree::build_by_ordered_float(vec![]))
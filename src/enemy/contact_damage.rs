@@ -1,5 +1,7 @@
-use crate::prelude::*;
+use crate::actor::DamageEvent;
+use crate::enemy::*;
 
+/// Damage dealt to the player on touch, written from the enemy's attributes.
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct ContactDamage(pub f32);
@@ -9,3 +11,63 @@ impl Default for ContactDamage {
         ContactDamage(1.0)
     }
 }
+
+/// How overlapping enemies hurt the player.
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+#[insert_resource(plugin = EnemyPlugin)]
+pub enum ContactDamageModel {
+    /// [`ContactDamage`] lands as a single hit, the player's i-frames keep it from landing
+    /// again every frame.
+    #[default]
+    PerHit,
+    /// [`ContactDamage`] is drained per second of overlap.
+    PerSecond,
+}
+
+impl ContactDamageModel {
+    /// Damage `source` deals `target` for one frame of overlap lasting `delta_secs`.
+    pub fn damage_event(
+        &self,
+        source: Entity,
+        target: Entity,
+        damage: f32,
+        delta_secs: f32,
+    ) -> DamageEvent {
+        match self {
+            ContactDamageModel::PerHit => DamageEvent::new(source, target, damage),
+            // A drain lands every frame, i-frames would throw away all but the first tick
+            ContactDamageModel::PerSecond => {
+                DamageEvent::new(source, target, damage * delta_secs).over_time()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENEMY: Entity = Entity::from_raw(1);
+    const PLAYER: Entity = Entity::from_raw(2);
+
+    #[test]
+    fn per_hit_lands_the_full_damage_as_a_hit() {
+        let event = ContactDamageModel::PerHit.damage_event(ENEMY, PLAYER, 10.0, 0.5);
+
+        assert_eq!(event.source, ENEMY);
+        assert_eq!(event.target, PLAYER);
+        assert_eq!(event.amount, 10.0);
+        assert!(!event.over_time);
+    }
+
+    #[test]
+    fn per_second_drains_a_share_of_the_damage_over_time() {
+        let event = ContactDamageModel::PerSecond.damage_event(ENEMY, PLAYER, 10.0, 0.5);
+
+        assert_eq!(event.source, ENEMY);
+        assert_eq!(event.target, PLAYER);
+        assert_eq!(event.amount, 5.0);
+        assert!(event.over_time);
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// Fraction of knockback velocity kept after one second, applied continuously so it
+/// decays the same at any frame rate.
+const KNOCKBACK_RETAINED_PER_SECOND: f32 = 0.002;
+/// How long a hit stops the enemy from steering.
+const HIT_STUN_SECONDS: f32 = 0.15;
+
+/// Velocity from being hit, added on top of the AI's [`Velocity`](super::Velocity) and
+/// decaying back to zero.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Knockback {
+    pub velocity: Vec3,
+    pub stun: Timer,
+}
+
+impl Default for Knockback {
+    fn default() -> Self {
+        let mut stun = Timer::from_seconds(HIT_STUN_SECONDS, TimerMode::Once);
+        stun.tick(stun.duration());
+        Knockback {
+            velocity: Vec3::ZERO,
+            stun,
+        }
+    }
+}
+
+impl Knockback {
+    /// Pushes the enemy by `impulse` and restarts the hit-stun.
+    pub fn apply_impulse(&mut self, impulse: Vec3) {
+        self.velocity += impulse;
+        self.stun.reset();
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        !self.stun.finished()
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.stun.tick(delta);
+        self.velocity *= KNOCKBACK_RETAINED_PER_SECOND.powf(delta.as_secs_f32());
+        if self.velocity.length_squared() < 1.0 {
+            self.velocity = Vec3::ZERO;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn knocked_back() -> Knockback {
+        let mut knockback = Knockback::default();
+        knockback.apply_impulse(Vec3::new(400.0, -200.0, 0.0));
+        knockback
+    }
+
+    #[test]
+    fn decay_is_the_same_at_any_frame_rate() {
+        let mut at_60 = knocked_back();
+        at_60.tick(Duration::from_secs_f32(1.0 / 60.0));
+        at_60.tick(Duration::from_secs_f32(1.0 / 60.0));
+
+        let mut at_30 = knocked_back();
+        at_30.tick(Duration::from_secs_f32(1.0 / 30.0));
+
+        assert!(at_30.velocity.length() < 400.0);
+        assert!(
+            at_60.velocity.abs_diff_eq(at_30.velocity, 0.01),
+            "{} != {}",
+            at_60.velocity,
+            at_30.velocity
+        );
+        assert_eq!(at_60.is_stunned(), at_30.is_stunned());
+    }
+}
@@ -11,15 +11,32 @@ pub(crate) use enemy_ai::*;
 mod velocity;
 pub(crate) use velocity::*;
 
+mod knockback;
+pub(crate) use knockback::*;
+
 mod collision;
 pub(crate) use collision::*;
 
 mod assets;
 pub(crate) use assets::*;
 
+mod archetype;
+pub(crate) use archetype::*;
+
+mod rank;
+pub(crate) use rank::*;
+
 #[derive(Component, Reflect, Debug, Default)]
 #[reflect(Component)]
-#[require(Actor, ContactDamage, EnemyAi, Velocity)]
+#[require(
+    Actor,
+    ContactDamage,
+    EnemyAi,
+    Velocity,
+    Knockback,
+    EnemyArchetype,
+    EnemyRank
+)]
 pub struct Enemy;
 
 #[add_plugin(to_group = EntityPlugins)]
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+/// How dangerous an enemy is, weapons that prioritise tougher enemies sort on this.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component)]
+pub enum EnemyRank {
+    #[default]
+    Normal,
+    Elite,
+    Boss,
+}
+
+impl EnemyRank {
+    /// Multiplier on the archetype's health and contact damage.
+    pub fn stat_multiplier(&self) -> f32 {
+        match self {
+            EnemyRank::Normal => 1.0,
+            EnemyRank::Elite => 4.0,
+            EnemyRank::Boss => 20.0,
+        }
+    }
+
+    /// Sprite tint so tougher enemies stand out in a crowd.
+    pub fn tint(&self) -> Color {
+        match self {
+            EnemyRank::Normal => Color::WHITE,
+            EnemyRank::Elite => Color::srgb(1.0, 0.8, 0.3),
+            EnemyRank::Boss => Color::srgb(1.0, 0.4, 0.4),
+        }
+    }
+}
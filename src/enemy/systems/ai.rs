@@ -7,16 +7,24 @@ use crate::actor::*;
 use crate::enemy::*;
 use crate::player::*;
 
-#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = on_timer(Duration::from_secs_f32(0.2)), run_if = in_state(GameState::InGame))]
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = on_timer(Duration::from_secs_f32(0.2)), run_if = in_state(InGameState::Playing))]
 fn update_enemy_ai(
     mut params: ParamSet<(
         Query<
-            (Entity, &Transform, &EnemyAi, &Speed, &mut Velocity),
+            (
+                Entity,
+                &Transform,
+                &EnemyAi,
+                &Speed,
+                &StatusEffects,
+                &Knockback,
+                &mut Velocity,
+            ),
             (With<Enemy>, Without<Player>),
         >,
         Query<(Entity, &Transform), (With<Enemy>, Without<Player>)>,
     )>,
-    player_query: Query<&Transform, With<Player>>,
+    player_query: Query<&Transform, (With<Player>, Without<Downed>)>,
     tree: Res<EnemyKdTree>,
 ) {
     // Early out if no players at all
@@ -37,7 +45,16 @@ fn update_enemy_ai(
     let max_speed = 200.0;
     let smoothing_factor = 0.15;
 
-    for (entity, transform, enemy_ai, speed, mut velocity) in params.p0().iter_mut() {
+    for (entity, transform, enemy_ai, speed, effects, knockback, mut velocity) in
+        params.p0().iter_mut()
+    {
+        // Stunned, frozen or reeling enemies stop dead and don't steer, slows come through
+        // `Speed`
+        if effects.suppresses_ai() || knockback.is_stunned() {
+            velocity.0 = Vec3::ZERO;
+            continue;
+        }
+
         match enemy_ai.0 {
             EnemyAiType::Basic => {
                 let pos_2d = [transform.translation.x, transform.translation.y];
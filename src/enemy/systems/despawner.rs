@@ -1,11 +1,52 @@
 use crate::actor::*;
+use crate::camera::{HitStop, ShakeCamera};
 use crate::enemy::*;
 use crate::prelude::*;
 
-#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(GameState::InGame))]
-fn despawn_dead_enemies(query: Query<(Entity, &Health), With<Enemy>>, mut commands: Commands) {
-    for (entity, health) in query.iter() {
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(InGameState::Playing))]
+fn despawn_dead_enemies(
+    query: Query<
+        (
+            Entity,
+            &Health,
+            &GlobalTransform,
+            &EnemyArchetype,
+            &EnemyRank,
+        ),
+        With<Enemy>,
+    >,
+    mut died: EventWriter<EnemyDied>,
+    mut hit_stop: EventWriter<HitStop>,
+    mut shake: EventWriter<ShakeCamera>,
+    mut commands: Commands,
+) {
+    for (entity, health, global_transform, archetype, rank) in query.iter() {
         if health.current <= 0.0 {
+            died.write(EnemyDied {
+                entity,
+                position: global_transform.translation(),
+                archetype: *archetype,
+            });
+
+            // Tougher kills land harder
+            match rank {
+                EnemyRank::Normal => {}
+                EnemyRank::Elite => {
+                    hit_stop.write(HitStop {
+                        duration: 0.06,
+                        time_scale: 0.1,
+                    });
+                    shake.write(ShakeCamera(0.4));
+                }
+                EnemyRank::Boss => {
+                    hit_stop.write(HitStop {
+                        duration: 0.2,
+                        time_scale: 0.05,
+                    });
+                    shake.write(ShakeCamera(0.8));
+                }
+            }
+
             commands.entity(entity).despawn();
         }
     }
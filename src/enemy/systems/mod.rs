@@ -8,19 +8,44 @@ mod ai;
 mod despawner;
 mod spawner;
 
-#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(GameState::InGame))]
-fn move_enemies(time: Res<Time>, mut query: Query<(&mut Transform, &Velocity), With<Enemy>>) {
-    for (mut transform, velocity) in &mut query {
-        transform.translation += velocity.0 * time.delta_secs();
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(InGameState::Playing))]
+fn move_enemies(
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &Velocity, &mut Knockback, &StatusEffects), With<Enemy>>,
+) {
+    for (mut transform, velocity, mut knockback, effects) in &mut query {
+        knockback.tick(time.delta());
+
+        // The AI only runs a few times a second, don't wait for it to notice a stun
+        let mut motion = knockback.velocity;
+        if !knockback.is_stunned() && !effects.suppresses_ai() {
+            motion += velocity.0;
+        }
+
+        transform.translation += motion * time.delta_secs();
+    }
+}
+
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(InGameState::Playing))]
+fn apply_enemy_attributes(
+    mut query: Query<(&Attributes, &mut ContactDamage), (With<Enemy>, Changed<Attributes>)>,
+) {
+    for (attributes, mut contact_damage) in query.iter_mut() {
+        contact_damage.0 = attributes.get(Attribute::ContactDamage);
     }
 }
 
-#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(GameState::InGame))]
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(InGameState::Playing))]
 fn apply_contact_damage(
-    mut player_query: Query<(&Transform, &Aabb, &mut Health), (With<Player>, Without<Enemy>)>,
+    player_query: Query<
+        (Entity, &Transform, &Aabb),
+        (With<Player>, Without<Downed>, Without<Enemy>),
+    >,
     enemy_query: Query<(&Transform, &Aabb, &ContactDamage), (With<Enemy>, Without<Player>)>,
     tree: Res<EnemyKdTree>,
     time: Res<Time>,
+    model: Res<ContactDamageModel>,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
     fn aabb_intersects(a: &Aabb, a_pos: Vec3, b: &Aabb, b_pos: Vec3) -> bool {
         let a_center = a.center + Vec3A::new(a_pos.x, a_pos.y, a_pos.z);
@@ -36,7 +61,7 @@ fn apply_contact_damage(
             && (delta.z <= a_half.z + b_half.z)
     }
 
-    for (player_transform, player_aabb, mut player_health) in player_query.iter_mut() {
+    for (player_entity, player_transform, player_aabb) in player_query.iter() {
         let search_radius =
             (player_aabb.half_extents.x.max(player_aabb.half_extents.y)) * 2.0 + 50.0;
 
@@ -56,14 +81,19 @@ fn apply_contact_damage(
                     player_aabb,
                     player_transform.translation,
                 ) {
-                    player_health.current -= contact_damage.0 * time.delta_secs();
+                    damage_events.write(model.damage_event(
+                        enemy_collision.entity,
+                        player_entity,
+                        contact_damage.0,
+                        time.delta_secs(),
+                    ));
                 }
             }
         }
     }
 }
 
-#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(GameState::InGame))]
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(InGameState::Playing))]
 fn update_enemy_kd_tree(
     mut tree: ResMut<EnemyKdTree>,
     enemy_query: Query<(&GlobalTransform, Entity), With<Enemy>>,
@@ -1,11 +1,10 @@
-use bevy::platform::collections::HashMap;
 use rand::Rng;
 
-use crate::actor::*;
-use crate::animated_sprite::*;
 use crate::camera::MainCamera;
 use crate::enemy::*;
-use crate::player::*;
+
+/// Chance for each spawned enemy to be an elite.
+const ELITE_CHANCE: f64 = 0.02;
 
 #[derive(Resource)]
 #[insert_resource(plugin = EnemyPlugin)]
@@ -35,7 +34,7 @@ fn random_point_around_camera(camera_pos: &Vec3, min_radius: f32, max_radius: f3
     Vec3::new(x, y, 0.0)
 }
 
-#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(GameState::InGame))]
+#[add_system(schedule = Update, plugin = EnemyPlugin, run_if = in_state(InGameState::Playing))]
 fn spawn_enemies(
     mut commands: Commands,
     time: Res<Time>,
@@ -71,39 +70,13 @@ fn spawn_enemies(
             let spawn_pos =
                 random_point_around_camera(&camera_transform.translation, min_radius, max_radius);
 
-            commands.spawn((
-                Enemy,
-                Name::new("Enemy"),
-                TeamFlag(Team::Enemy),
-                Sprite {
-                    image: assets.sprite.clone(),
-                    custom_size: Some(Vec2::new(32.0, 32.0)),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: assets.layout.clone(),
-                        index: 0,
-                    }),
-                    ..default()
-                },
-                AnimatedSprite {
-                    animations: {
-                        let mut map = HashMap::new();
-                        map.insert(
-                            "move".to_string(),
-                            Animation {
-                                frames: (0..5).collect(),
-                                durations: vec![0.1; 6],
-                            },
-                        );
-                        map
-                    },
-                    current_animation: "move".to_string(),
-                    current_frame: 0,
-                    timer: Timer::from_seconds(0.1, TimerMode::Repeating),
-                },
-                Speed(64.0),
-                Aabb::from_min_max(Vec3::new(-16.0, -16.0, 0.0), Vec3::new(16.0, 16.0, 0.0)),
-                Transform::from_translation(spawn_pos),
-            ));
+            let rank = if rand::thread_rng().gen_bool(ELITE_CHANCE) {
+                EnemyRank::Elite
+            } else {
+                EnemyRank::Normal
+            };
+
+            commands.spawn(EnemyArchetype::Slime.bundle(rank, &assets, spawn_pos));
         }
 
         info!(
@@ -122,3 +95,9 @@ fn spawn_enemies(
         spawn_timer.timer.reset();
     }
 }
+
+#[add_system(schedule = OnExit(GameState::InGame), plugin = EnemyPlugin)]
+fn reset_enemy_resources(mut commands: Commands) {
+    commands.insert_resource(EnemySpawnTimer::default());
+    commands.insert_resource(EnemyKdTree::default());
+}
@@ -1,13 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::input::{KeyBindings, PlayerControls};
 use crate::prelude::*;
 
-#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
 pub enum Action {
     #[actionlike(DualAxis)]
     Move,
+    Dash,
 }
 
 impl Action {
     pub fn default_input_map() -> InputMap<Self> {
-        InputMap::default().with_dual_axis(Self::Move, VirtualDPad::wasd())
+        KeyBindings::default().input_map(PlayerControls::Any)
+    }
+}
+
+/// Global menu navigation, stored as a resource rather than on an entity. Not rebindable,
+/// so the settings screen stays reachable whatever the [`KeyBindings`] are.
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+pub enum MenuAction {
+    Up,
+    Down,
+    Confirm,
+    Reroll,
+    Banish,
+    Skip,
+    Pause,
+    CycleCamera,
+}
+
+impl MenuAction {
+    pub fn default_input_map() -> InputMap<Self> {
+        InputMap::new([
+            (Self::Up, KeyCode::ArrowUp),
+            (Self::Up, KeyCode::KeyW),
+            (Self::Down, KeyCode::ArrowDown),
+            (Self::Down, KeyCode::KeyS),
+            (Self::Confirm, KeyCode::Enter),
+            (Self::Confirm, KeyCode::Space),
+            (Self::Reroll, KeyCode::KeyR),
+            (Self::Banish, KeyCode::KeyB),
+            (Self::Skip, KeyCode::KeyX),
+            (Self::Pause, KeyCode::Escape),
+            (Self::CycleCamera, KeyCode::KeyC),
+        ])
+        .with(Self::Up, GamepadButton::DPadUp)
+        .with(Self::Down, GamepadButton::DPadDown)
+        .with(Self::Confirm, GamepadButton::South)
+        .with(Self::Reroll, GamepadButton::West)
+        .with(Self::Banish, GamepadButton::North)
+        .with(Self::Skip, GamepadButton::East)
+        .with(Self::Pause, GamepadButton::Start)
+        .with(Self::CycleCamera, GamepadButton::Select)
     }
 }
@@ -0,0 +1,314 @@
+use std::fs;
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::camera::{PAN_DOWN_KEYS, PAN_LEFT_KEYS, PAN_RIGHT_KEYS, PAN_UP_KEYS};
+use crate::input::*;
+
+const BINDINGS_FILE: &str = "bindings.ron";
+
+/// A single rebindable input, `Move` is split into its four directions.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Dash,
+    DashGamepad,
+}
+
+impl Binding {
+    pub const ALL: [Binding; 6] = [
+        Binding::MoveUp,
+        Binding::MoveDown,
+        Binding::MoveLeft,
+        Binding::MoveRight,
+        Binding::Dash,
+        Binding::DashGamepad,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Binding::MoveUp => "Move up",
+            Binding::MoveDown => "Move down",
+            Binding::MoveLeft => "Move left",
+            Binding::MoveRight => "Move right",
+            Binding::Dash => "Dash",
+            Binding::DashGamepad => "Dash (gamepad)",
+        }
+    }
+
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::DashGamepad)
+    }
+}
+
+/// Which devices drive a player's [`Action`]s.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum PlayerControls {
+    /// Keyboard and every gamepad, for single player.
+    #[default]
+    Any,
+    Keyboard,
+    /// A single gamepad, `None` until a free one is connected.
+    Gamepad(Option<Entity>),
+}
+
+/// Player controls, loaded from the user's data directory at startup and saved back
+/// whenever they change. The left stick and D-pad always move on top of these.
+/// Shared by every local player, each only gets the half their [`PlayerControls`] allow.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_up: KeyCode,
+    pub move_down: KeyCode,
+    pub move_left: KeyCode,
+    pub move_right: KeyCode,
+    pub dash: KeyCode,
+    pub dash_gamepad: GamepadButton,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            move_up: KeyCode::KeyW,
+            move_down: KeyCode::KeyS,
+            move_left: KeyCode::KeyA,
+            move_right: KeyCode::KeyD,
+            dash: KeyCode::Space,
+            dash_gamepad: GamepadButton::South,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn input_map(&self, controls: PlayerControls) -> InputMap<Action> {
+        match controls {
+            PlayerControls::Any => self.with_gamepad_inputs(self.with_keyboard_inputs(default())),
+            PlayerControls::Keyboard => self.with_keyboard_inputs(default()),
+            PlayerControls::Gamepad(Some(gamepad)) => {
+                self.with_gamepad_inputs(default()).with_gamepad(gamepad)
+            }
+            // Without a gamepad set the map would listen to all of them
+            PlayerControls::Gamepad(None) => default(),
+        }
+    }
+
+    fn with_keyboard_inputs(&self, input_map: InputMap<Action>) -> InputMap<Action> {
+        input_map
+            .with_dual_axis(
+                Action::Move,
+                VirtualDPad::new(
+                    self.move_up,
+                    self.move_down,
+                    self.move_left,
+                    self.move_right,
+                ),
+            )
+            .with(Action::Dash, self.dash)
+    }
+
+    fn with_gamepad_inputs(&self, input_map: InputMap<Action>) -> InputMap<Action> {
+        input_map
+            .with_dual_axis(Action::Move, GamepadStick::LEFT)
+            .with_dual_axis(Action::Move, VirtualDPad::dpad())
+            .with(Action::Dash, self.dash_gamepad)
+    }
+
+    pub fn key(&self, binding: Binding) -> Option<KeyCode> {
+        match binding {
+            Binding::MoveUp => Some(self.move_up),
+            Binding::MoveDown => Some(self.move_down),
+            Binding::MoveLeft => Some(self.move_left),
+            Binding::MoveRight => Some(self.move_right),
+            Binding::Dash => Some(self.dash),
+            Binding::DashGamepad => None,
+        }
+    }
+
+    pub fn set_key(&mut self, binding: Binding, key: KeyCode) {
+        match binding {
+            Binding::MoveUp => self.move_up = key,
+            Binding::MoveDown => self.move_down = key,
+            Binding::MoveLeft => self.move_left = key,
+            Binding::MoveRight => self.move_right = key,
+            Binding::Dash => self.dash = key,
+            Binding::DashGamepad => {}
+        }
+    }
+
+    pub fn describe(&self, binding: Binding) -> String {
+        match binding {
+            Binding::DashGamepad => format!("{:?}", self.dash_gamepad),
+            _ => self
+                .key(binding)
+                .map(|key| format!("{:?}", key))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Keys bound twice, or that the free camera also pans with, as readable messages.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+
+        let keyed: Vec<(Binding, KeyCode)> = Binding::ALL
+            .into_iter()
+            .filter_map(|binding| self.key(binding).map(|key| (binding, key)))
+            .collect();
+
+        for (index, (binding, key)) in keyed.iter().enumerate() {
+            for (other, other_key) in keyed.iter().skip(index + 1) {
+                if key == other_key {
+                    conflicts.push(format!(
+                        "{:?} is bound to both {} and {}",
+                        key,
+                        binding.name(),
+                        other.name()
+                    ));
+                }
+            }
+
+            let pans = [PAN_UP_KEYS, PAN_DOWN_KEYS, PAN_LEFT_KEYS, PAN_RIGHT_KEYS]
+                .iter()
+                .any(|keys| keys.contains(key));
+            if pans {
+                conflicts.push(format!(
+                    "{:?} ({}) also pans the free camera (dev builds)",
+                    key,
+                    binding.name()
+                ));
+            }
+        }
+
+        conflicts
+    }
+
+    fn path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "Shadows Over Ebonshire")
+            .map(|dirs| dirs.data_dir().join(BINDINGS_FILE))
+    }
+
+    /// Reads saved bindings, falling back to the defaults when there are none or they
+    /// can't be read.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return KeyBindings::default();
+        };
+
+        let Ok(contents) = fs::read_to_string(&path) else {
+            info!("No saved bindings at {}, using defaults", path.display());
+            return KeyBindings::default();
+        };
+
+        match ron::from_str(&contents) {
+            Ok(bindings) => bindings,
+            Err(error) => {
+                warn!("Couldn't parse {}: {}", path.display(), error);
+                KeyBindings::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())
+            .and_then(|contents| {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+                }
+                fs::write(&path, contents).map_err(|error| error.to_string())
+            });
+
+        match result {
+            Ok(()) => info!("Saved bindings to {}", path.display()),
+            Err(error) => warn!("Couldn't save bindings to {}: {}", path.display(), error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bindings clear of each other and of the camera pan keys.
+    fn ijkl() -> KeyBindings {
+        KeyBindings {
+            move_up: KeyCode::KeyI,
+            move_down: KeyCode::KeyK,
+            move_left: KeyCode::KeyJ,
+            move_right: KeyCode::KeyL,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn distinct_keys_do_not_conflict() {
+        assert!(ijkl().conflicts().is_empty());
+    }
+
+    #[test]
+    fn a_key_bound_twice_conflicts() {
+        let mut bindings = ijkl();
+        bindings.set_key(Binding::Dash, KeyCode::KeyI);
+
+        assert_eq!(
+            bindings.conflicts(),
+            vec!["KeyI is bound to both Move up and Dash".to_string()]
+        );
+    }
+
+    #[test]
+    fn camera_pan_keys_conflict() {
+        let bindings = KeyBindings {
+            move_up: KeyCode::ArrowUp,
+            ..ijkl()
+        };
+
+        assert_eq!(
+            bindings.conflicts(),
+            vec!["ArrowUp (Move up) also pans the free camera (dev builds)".to_string()]
+        );
+    }
+
+    #[test]
+    fn default_movement_shares_the_camera_pan_keys() {
+        assert_eq!(KeyBindings::default().conflicts().len(), 4);
+    }
+
+    #[test]
+    fn bindings_round_trip_through_ron() {
+        let bindings = KeyBindings {
+            dash: KeyCode::ShiftLeft,
+            dash_gamepad: GamepadButton::East,
+            ..ijkl()
+        };
+
+        let ron = ron::ser::to_string_pretty(&bindings, ron::ser::PrettyConfig::default())
+            .expect("bindings serialize");
+        let loaded: KeyBindings = ron::from_str(&ron).expect("bindings deserialize");
+
+        assert_eq!(loaded, bindings);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_the_defaults() {
+        let loaded: KeyBindings = ron::from_str("(dash: KeyE)").expect("bindings deserialize");
+
+        assert_eq!(
+            loaded,
+            KeyBindings {
+                dash: KeyCode::KeyE,
+                ..default()
+            }
+        );
+    }
+}
@@ -3,13 +3,22 @@ use crate::prelude::*;
 mod action;
 pub(crate) use action::*;
 
+mod bindings;
+pub(crate) use bindings::*;
+
 #[add_plugin(to_group = CorePlugins)]
 pub struct InputPlugin;
 
 #[butler_plugin]
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(InputManagerPlugin::<Action>::default());
+        app.add_plugins((
+            InputManagerPlugin::<Action>::default(),
+            InputManagerPlugin::<MenuAction>::default(),
+        ))
+        .init_resource::<ActionState<MenuAction>>()
+        .insert_resource(MenuAction::default_input_map())
+        .insert_resource(KeyBindings::load());
     }
 }
 
@@ -1,11 +1,33 @@
 use crate::actor::*;
+use crate::camera::CameraMode;
 use crate::input::*;
+use crate::player::{Downed, PlayerSlot};
 
-#[add_system(schedule = Update, plugin = InputPlugin, run_if = in_state(GameState::InGame))]
-fn handle_input(mut query: Query<(&mut Transform, &ActionState<Action>, &Speed)>, time: Res<Time>) {
+#[add_system(schedule = Update, plugin = InputPlugin, run_if = in_state(InGameState::Playing), run_if = not(resource_equals(CameraMode::Free)))]
+fn handle_input(
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Facing,
+            &ActionState<Action>,
+            &Speed,
+            &StatusEffects,
+        ),
+        Without<Downed>,
+    >,
+    time: Res<Time>,
+) {
     debug!("Handling player input");
-    for (mut transform, action_state, speed) in query.iter_mut() {
+    for (mut transform, mut facing, action_state, speed, effects) in query.iter_mut() {
+        if effects.suppresses_ai() {
+            continue;
+        }
+
         let mut movement = action_state.clamped_axis_pair(&Action::Move);
+        if movement != Vec2::ZERO {
+            facing.0 = movement.normalize();
+        }
+
         movement *= time.delta_secs();
         movement *= speed.0;
 
@@ -13,3 +35,74 @@ fn handle_input(mut query: Query<(&mut Transform, &ActionState<Action>, &Speed)>
         transform.translation.y += movement.y;
     }
 }
+
+/// Rebuilds every player's input map from the current bindings and their controls.
+#[add_system(schedule = Update, plugin = InputPlugin, after = assign_gamepads)]
+fn apply_key_bindings(
+    bindings: Res<KeyBindings>,
+    mut query: Query<(Ref<PlayerControls>, &mut InputMap<Action>)>,
+) {
+    for (controls, mut input_map) in query.iter_mut() {
+        if bindings.is_changed() || controls.is_changed() || input_map.is_added() {
+            *input_map = bindings.input_map(*controls);
+        }
+    }
+}
+
+/// Hands connected gamepads out to players waiting on one, in slot order, and takes
+/// back gamepads that were disconnected.
+#[add_system(schedule = Update, plugin = InputPlugin, run_if = in_state(GameState::InGame))]
+fn assign_gamepads(
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut query: Query<(&PlayerSlot, &mut PlayerControls)>,
+) {
+    let mut players: Vec<_> = query.iter_mut().collect();
+    players.sort_by_key(|(slot, _)| slot.0);
+
+    for (slot, controls) in players.iter_mut() {
+        if let PlayerControls::Gamepad(Some(gamepad)) = **controls {
+            if !gamepads.contains(gamepad) {
+                info!("Player {} lost their gamepad", slot.number());
+                **controls = PlayerControls::Gamepad(None);
+            }
+        }
+    }
+
+    let taken: Vec<Entity> = players
+        .iter()
+        .filter_map(|(_, controls)| match **controls {
+            PlayerControls::Gamepad(gamepad) => gamepad,
+            _ => None,
+        })
+        .collect();
+    let mut free = gamepads.iter().filter(|gamepad| !taken.contains(gamepad));
+
+    for (slot, controls) in players.iter_mut() {
+        if **controls != PlayerControls::Gamepad(None) {
+            continue;
+        }
+
+        let Some(gamepad) = free.next() else {
+            break;
+        };
+        info!("Assigned gamepad {} to player {}", gamepad, slot.number());
+        **controls = PlayerControls::Gamepad(Some(gamepad));
+    }
+}
+
+#[add_system(schedule = Update, plugin = InputPlugin, run_if = resource_changed::<KeyBindings>)]
+fn report_binding_conflicts(bindings: Res<KeyBindings>) {
+    for conflict in bindings.conflicts() {
+        warn!("Binding conflict: {}", conflict);
+    }
+}
+
+#[add_system(
+    schedule = Update,
+    plugin = InputPlugin,
+    run_if = resource_changed::<KeyBindings>,
+    run_if = not(resource_added::<KeyBindings>)
+)]
+fn save_key_bindings(bindings: Res<KeyBindings>) {
+    bindings.save();
+}
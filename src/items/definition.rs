@@ -0,0 +1,69 @@
+use bevy::ecs::system::SystemParam;
+use serde::Deserialize;
+
+use crate::items::*;
+
+/// Every passive item's level table, loaded from `data/items.ron`.
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct PassiveItemCatalog {
+    pub items: Vec<PassiveItemDefinition>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct ItemAssets {
+    #[asset(path = "data/items.ron")]
+    pub catalog: Handle<PassiveItemCatalog>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PassiveItemDefinition {
+    pub item: PassiveItem,
+    pub description: String,
+    /// Bonus granted by each level, the item's max level is the length of this list.
+    pub levels: Vec<f32>,
+}
+
+impl PassiveItemDefinition {
+    pub fn max_level(&self) -> u32 {
+        self.levels.len() as u32
+    }
+
+    /// Total bonus at `level`, the sum of every level up to it.
+    pub fn value_at(&self, level: u32) -> f32 {
+        self.levels.iter().take(level as usize).sum()
+    }
+}
+
+/// Looks passive item definitions up from the loaded catalog.
+#[derive(SystemParam)]
+pub struct PassiveItemDefinitions<'w> {
+    item_assets: Option<Res<'w, ItemAssets>>,
+    catalogs: Res<'w, Assets<PassiveItemCatalog>>,
+}
+
+impl PassiveItemDefinitions<'_> {
+    pub fn get(&self, item: PassiveItem) -> Option<&PassiveItemDefinition> {
+        let catalog = self.catalogs.get(&self.item_assets.as_ref()?.catalog)?;
+        catalog
+            .items
+            .iter()
+            .find(|definition| definition.item == item)
+    }
+
+    pub fn modifiers(&self, items: &PassiveItems) -> StatModifiers {
+        let mut modifiers = StatModifiers::default();
+        for (item, level) in items.0.iter() {
+            if let Some(definition) = self.get(*item) {
+                modifiers.add(*item, definition.value_at(*level));
+            }
+        }
+
+        modifiers
+    }
+
+    pub fn max_level(&self, item: PassiveItem) -> u32 {
+        self.get(item)
+            .map(|definition| definition.max_level())
+            .unwrap_or(0)
+    }
+}
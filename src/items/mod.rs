@@ -0,0 +1,117 @@
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+pub const MAX_PASSIVE_ITEMS: usize = 6;
+
+/// Passive items modify every weapon their owner has equipped, their values per level
+/// live in `data/items.ron`.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum PassiveItem {
+    Might,
+    Area,
+    Cooldown,
+    Amount,
+    ProjectileSpeed,
+    Duration,
+    Magnet,
+    Luck,
+}
+
+impl PassiveItem {
+    pub const ALL: [PassiveItem; 8] = [
+        PassiveItem::Might,
+        PassiveItem::Area,
+        PassiveItem::Cooldown,
+        PassiveItem::Amount,
+        PassiveItem::ProjectileSpeed,
+        PassiveItem::Duration,
+        PassiveItem::Magnet,
+        PassiveItem::Luck,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PassiveItem::Might => "Spinach",
+            PassiveItem::Area => "Candelabrador",
+            PassiveItem::Cooldown => "Empty Tome",
+            PassiveItem::Amount => "Duplicator",
+            PassiveItem::ProjectileSpeed => "Bracer",
+            PassiveItem::Duration => "Spellbinder",
+            PassiveItem::Magnet => "Attractorb",
+            PassiveItem::Luck => "Clover",
+        }
+    }
+}
+
+/// Passive items held and their levels. Taking an item out of the list takes its bonus
+/// away, [`StatModifiers`] are recomputed from whatever is left.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct PassiveItems(pub Vec<(PassiveItem, u32)>);
+
+impl PassiveItems {
+    pub fn level(&self, item: PassiveItem) -> Option<u32> {
+        self.0
+            .iter()
+            .find(|(held, _)| *held == item)
+            .map(|(_, level)| *level)
+    }
+}
+
+/// Sum of every passive item held, recomputed from scratch whenever [`PassiveItems`]
+/// changes. Fractions are bonuses on top of the base, so zero leaves stats untouched.
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct StatModifiers {
+    pub might: f32,
+    pub area: f32,
+    /// Fraction taken off every cooldown and attack interval.
+    pub cooldown: f32,
+    /// Extra satellites, targets or projectiles.
+    pub amount: f32,
+    pub projectile_speed: f32,
+    pub duration: f32,
+    pub magnet: f32,
+    pub luck: f32,
+}
+
+impl StatModifiers {
+    fn add(&mut self, item: PassiveItem, value: f32) {
+        let stat = match item {
+            PassiveItem::Might => &mut self.might,
+            PassiveItem::Area => &mut self.area,
+            PassiveItem::Cooldown => &mut self.cooldown,
+            PassiveItem::Amount => &mut self.amount,
+            PassiveItem::ProjectileSpeed => &mut self.projectile_speed,
+            PassiveItem::Duration => &mut self.duration,
+            PassiveItem::Magnet => &mut self.magnet,
+            PassiveItem::Luck => &mut self.luck,
+        };
+        *stat += value;
+    }
+
+    /// Multiplier for attack intervals, capped so cooldowns never reach zero.
+    pub fn cooldown_multiplier(&self) -> f32 {
+        (1.0 - self.cooldown).max(0.1)
+    }
+}
+
+#[add_plugin(to_group = EntityPlugins)]
+struct ItemsPlugin;
+
+#[butler_plugin]
+impl Plugin for ItemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<PassiveItemCatalog>::new(&["items.ron"]))
+            .configure_loading_state(
+                LoadingStateConfig::new(GameState::Loading).load_collection::<ItemAssets>(),
+            );
+    }
+}
+
+mod definition;
+pub(crate) use definition::*;
+
+pub(crate) mod systems;
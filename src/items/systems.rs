@@ -0,0 +1,29 @@
+use crate::actor::*;
+use crate::items::*;
+
+#[add_system(schedule = Update, plugin = ItemsPlugin, run_if = in_state(GameState::InGame))]
+pub(crate) fn recompute_stat_modifiers(
+    definitions: PassiveItemDefinitions,
+    mut query: Query<
+        (&PassiveItems, &mut StatModifiers, Option<&mut Attributes>),
+        Changed<PassiveItems>,
+    >,
+) {
+    for (items, mut modifiers, attributes) in query.iter_mut() {
+        // Only write when something actually moved so weapons aren't rebuilt for nothing
+        modifiers.set_if_neq(definitions.modifiers(items));
+
+        // Magnet is an actor stat rather than a weapon one, it goes through attributes so
+        // it stacks with upgrades and buffs
+        if let Some(mut attributes) = attributes {
+            let source = ModifierSource::Passive(PassiveItem::Magnet);
+            attributes.remove_source(source);
+            if modifiers.magnet != 0.0 {
+                attributes.add_modifier(
+                    Attribute::PickupRadius,
+                    AttributeModifier::percent_add(source, modifiers.magnet),
+                );
+            }
+        }
+    }
+}
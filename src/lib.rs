@@ -6,8 +6,37 @@ use crate::prelude::*;
 pub enum GameState {
     #[default]
     Loading,
-    // MainMenu,
+    MainMenu,
     InGame,
+    GameOver,
+}
+
+/// Gameplay systems only run while [`InGameState::Playing`], anything else freezes the run.
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(GameState = GameState::InGame)]
+pub enum InGameState {
+    #[default]
+    Playing,
+    LevelUp,
+    Paused,
+}
+
+/// Which screen of the main menu is open.
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(GameState = GameState::MainMenu)]
+pub enum MainMenuState {
+    #[default]
+    Main,
+    Settings,
+}
+
+/// Which screen of the pause menu is open.
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(InGameState = InGameState::Paused)]
+pub enum PauseMenuState {
+    #[default]
+    Main,
+    Settings,
 }
 
 // Components
@@ -17,13 +46,19 @@ mod weapon;
 // Systems
 mod camera;
 mod input;
+mod run;
+mod settings;
+mod ui;
 mod world;
 
 // Entities
 mod actor;
 mod enemy;
 mod entity;
+mod items;
+mod pickups;
 mod player;
+mod progression;
 
 #[butler_plugin_group]
 #[add_plugin(to_plugin = Core)]
@@ -24,9 +24,13 @@ fn main() {
                 .set(ImagePlugin::default_nearest()),
         )
         .init_state::<shadows_over_ebonshire::GameState>()
+        .add_sub_state::<shadows_over_ebonshire::MainMenuState>()
+        .add_sub_state::<shadows_over_ebonshire::InGameState>()
+        .add_sub_state::<shadows_over_ebonshire::PauseMenuState>()
+        .enable_state_scoped_entities::<shadows_over_ebonshire::GameState>()
         .add_loading_state(
             LoadingState::new(shadows_over_ebonshire::GameState::Loading)
-                .continue_to_state(shadows_over_ebonshire::GameState::InGame),
+                .continue_to_state(shadows_over_ebonshire::GameState::MainMenu),
         )
         .add_plugins(shadows_over_ebonshire::Core)
         .run();
@@ -0,0 +1,101 @@
+use crate::entity::GameEntity;
+use crate::prelude::*;
+
+/// Gems are merged together once there are more than this many on the map.
+pub const MAX_XP_GEMS: usize = 400;
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum PickupKind {
+    Experience(f32),
+    Gold(u32),
+    Health(f32),
+    Magnet,
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XpGemTier {
+    Small,
+    Medium,
+    Large,
+    Huge,
+}
+
+impl XpGemTier {
+    pub fn from_value(value: f32) -> Self {
+        match value {
+            v if v < 5.0 => XpGemTier::Small,
+            v if v < 25.0 => XpGemTier::Medium,
+            v if v < 100.0 => XpGemTier::Large,
+            _ => XpGemTier::Huge,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            XpGemTier::Small => Color::srgb(0.3, 0.6, 1.0),
+            XpGemTier::Medium => Color::srgb(0.3, 1.0, 0.4),
+            XpGemTier::Large => Color::srgb(1.0, 0.3, 0.3),
+            XpGemTier::Huge => Color::srgb(0.8, 0.3, 1.0),
+        }
+    }
+
+    pub fn size(&self) -> f32 {
+        match self {
+            XpGemTier::Small => 8.0,
+            XpGemTier::Medium => 10.0,
+            XpGemTier::Large => 12.0,
+            XpGemTier::Huge => 16.0,
+        }
+    }
+}
+
+impl PickupKind {
+    pub fn sprite(&self) -> Sprite {
+        let (color, size) = match self {
+            PickupKind::Experience(value) => {
+                let tier = XpGemTier::from_value(*value);
+                (tier.color(), tier.size())
+            }
+            PickupKind::Gold(_) => (Color::srgb(1.0, 0.85, 0.1), 10.0),
+            PickupKind::Health(_) => (Color::srgb(1.0, 0.1, 0.2), 12.0),
+            PickupKind::Magnet => (Color::srgb(0.7, 0.7, 0.7), 14.0),
+        };
+
+        Sprite {
+            color,
+            custom_size: Some(Vec2::splat(size)),
+            ..default()
+        }
+    }
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(GameEntity, Name::new("Pickup"))]
+pub struct Pickup(pub PickupKind);
+
+impl Pickup {
+    /// Pickups only live for the duration of a run.
+    pub fn bundle(kind: PickupKind, position: Vec3) -> impl Bundle {
+        (
+            Pickup(kind),
+            StateScoped(GameState::InGame),
+            kind.sprite(),
+            Transform::from_translation(position.with_z(-1.0)),
+        )
+    }
+}
+
+/// Pickup is flying towards the given player.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Attracted {
+    pub target: Entity,
+    pub speed: f32,
+}
+
+#[butler_plugin]
+#[add_plugin(to_group = EntityPlugins)]
+struct PickupsPlugin;
+
+mod systems;
@@ -0,0 +1,208 @@
+use rand::Rng;
+
+use crate::actor::*;
+use crate::enemy::*;
+use crate::items::StatModifiers;
+use crate::pickups::*;
+use crate::player::*;
+
+const COLLECT_DISTANCE: f32 = 16.0;
+const ATTRACT_START_SPEED: f32 = 200.0;
+const ATTRACT_ACCELERATION: f32 = 1200.0;
+
+const GOLD_DROP_CHANCE: f64 = 0.05;
+const HEALTH_DROP_CHANCE: f64 = 0.01;
+const MAGNET_DROP_CHANCE: f64 = 0.002;
+
+#[add_system(schedule = Update, plugin = PickupsPlugin, run_if = in_state(InGameState::Playing))]
+fn spawn_drops(
+    mut died: EventReader<EnemyDied>,
+    mut commands: Commands,
+    player_query: Query<&StatModifiers, With<Player>>,
+) {
+    let mut rng = rand::thread_rng();
+
+    // Drops aren't owned by anyone, so the luckiest player decides
+    let luck = 1.0
+        + player_query
+            .iter()
+            .map(|modifiers| modifiers.luck)
+            .fold(0.0, f32::max) as f64;
+
+    for event in died.read() {
+        commands.spawn(Pickup::bundle(
+            PickupKind::Experience(event.archetype.experience()),
+            event.position,
+        ));
+
+        // Offset extra drops slightly so they don't stack on top of the gem
+        let offset = Vec3::new(rng.gen_range(-8.0..8.0), rng.gen_range(-8.0..8.0), 0.0);
+
+        if rng.gen_bool((GOLD_DROP_CHANCE * luck).min(1.0)) {
+            commands.spawn(Pickup::bundle(
+                PickupKind::Gold(rng.gen_range(1..=5)),
+                event.position + offset,
+            ));
+        } else if rng.gen_bool((HEALTH_DROP_CHANCE * luck).min(1.0)) {
+            commands.spawn(Pickup::bundle(
+                PickupKind::Health(25.0),
+                event.position + offset,
+            ));
+        } else if rng.gen_bool((MAGNET_DROP_CHANCE * luck).min(1.0)) {
+            commands.spawn(Pickup::bundle(PickupKind::Magnet, event.position + offset));
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = PickupsPlugin, run_if = in_state(InGameState::Playing))]
+fn attract_pickups(
+    mut commands: Commands,
+    player_query: Query<(Entity, &GlobalTransform, &PickupRadius), (With<Player>, Without<Downed>)>,
+    pickup_query: Query<(Entity, &GlobalTransform), (With<Pickup>, Without<Attracted>)>,
+) {
+    for (pickup_entity, pickup_transform) in pickup_query.iter() {
+        let pickup_pos = pickup_transform.translation().truncate();
+
+        let nearest = player_query
+            .iter()
+            .filter(|(_, player_transform, radius)| {
+                let radius = radius.0;
+                player_transform
+                    .translation()
+                    .truncate()
+                    .distance_squared(pickup_pos)
+                    <= radius * radius
+            })
+            .min_by(|(_, a, ..), (_, b, ..)| {
+                let a = a.translation().truncate().distance_squared(pickup_pos);
+                let b = b.translation().truncate().distance_squared(pickup_pos);
+                a.total_cmp(&b)
+            });
+
+        if let Some((player_entity, ..)) = nearest {
+            commands.entity(pickup_entity).insert(Attracted {
+                target: player_entity,
+                speed: ATTRACT_START_SPEED,
+            });
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = PickupsPlugin, run_if = in_state(InGameState::Playing), after = attract_pickups)]
+fn collect_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickup_query: Query<(Entity, &Pickup, &mut Transform, &mut Attracted)>,
+    mut player_query: Query<
+        (&GlobalTransform, &mut Experience, &mut Gold, &mut Health),
+        With<Player>,
+    >,
+    downed_query: Query<(), With<Downed>>,
+    gem_query: Query<(Entity, &Pickup), Without<Attracted>>,
+    coop: Res<CoopSettings>,
+) {
+    let mut shared_experience = 0.0;
+
+    for (pickup_entity, pickup, mut transform, mut attracted) in pickup_query.iter_mut() {
+        // Downed players drop whatever was flying towards them
+        let Some((player_transform, mut experience, mut gold, mut health)) = player_query
+            .get_mut(attracted.target)
+            .ok()
+            .filter(|_| !downed_query.contains(attracted.target))
+        else {
+            commands.entity(pickup_entity).remove::<Attracted>();
+            continue;
+        };
+
+        let to_player =
+            player_transform.translation().truncate() - transform.translation.truncate();
+        let step = attracted.speed * time.delta_secs();
+
+        if to_player.length() > COLLECT_DISTANCE.max(step) {
+            let movement = to_player.normalize() * step;
+            transform.translation.x += movement.x;
+            transform.translation.y += movement.y;
+            attracted.speed += ATTRACT_ACCELERATION * time.delta_secs();
+            continue;
+        }
+
+        match pickup.0 {
+            PickupKind::Experience(value) if coop.shared_experience => {
+                shared_experience += value;
+            }
+            PickupKind::Experience(value) => experience.0 += value,
+            PickupKind::Gold(amount) => gold.0 += amount,
+            PickupKind::Health(amount) => {
+                health.current = (health.current + amount).min(health.max);
+            }
+            PickupKind::Magnet => {
+                for (gem_entity, gem) in gem_query.iter() {
+                    if matches!(gem.0, PickupKind::Experience(_)) {
+                        commands.entity(gem_entity).insert(Attracted {
+                            target: attracted.target,
+                            speed: ATTRACT_START_SPEED,
+                        });
+                    }
+                }
+            }
+        }
+
+        commands.entity(pickup_entity).despawn();
+    }
+
+    if shared_experience > 0.0 {
+        for (_, mut experience, ..) in player_query.iter_mut() {
+            experience.0 += shared_experience;
+        }
+    }
+}
+
+/// Folds the gems furthest from any player into a single gem once there are too many,
+/// keeping the total experience on the map unchanged.
+#[add_system(schedule = Update, plugin = PickupsPlugin, run_if = in_state(InGameState::Playing))]
+fn merge_xp_gems(
+    mut commands: Commands,
+    gem_query: Query<(Entity, &Pickup, &GlobalTransform), Without<Attracted>>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) {
+    let mut gems: Vec<(Entity, f32, Vec3)> = gem_query
+        .iter()
+        .filter_map(|(entity, pickup, transform)| match pickup.0 {
+            PickupKind::Experience(value) => Some((entity, value, transform.translation())),
+            _ => None,
+        })
+        .collect();
+
+    if gems.len() <= MAX_XP_GEMS {
+        return;
+    }
+
+    let distance_to_players = |pos: Vec3| {
+        player_query
+            .iter()
+            .map(|player| player.translation().distance_squared(pos))
+            .fold(f32::MAX, f32::min)
+    };
+
+    gems.sort_by(|(_, _, a), (_, _, b)| {
+        distance_to_players(*b).total_cmp(&distance_to_players(*a))
+    });
+
+    let excess = gems.len() - MAX_XP_GEMS + 1;
+    let merged = &gems[..excess];
+
+    let total: f32 = merged.iter().map(|(_, value, _)| value).sum();
+    // The middle of the merged gems rather than any one of them, so nothing visibly jumps
+    let position = merged
+        .iter()
+        .map(|(_, _, position)| *position)
+        .sum::<Vec3>()
+        / merged.len() as f32;
+
+    for (entity, _, _) in merged {
+        commands.entity(*entity).despawn();
+    }
+
+    debug!("Merged {} xp gems into one worth {}", merged.len(), total);
+    commands.spawn(Pickup::bundle(PickupKind::Experience(total), position));
+}
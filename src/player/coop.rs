@@ -0,0 +1,166 @@
+use bevy::ecs::entity_disabling::Disabled;
+use bevy::ecs::query::QueryFilter;
+
+use crate::actor::*;
+use crate::input::PlayerControls;
+use crate::player::*;
+use crate::weapon::components::EquippedWeapons;
+
+pub const MAX_PLAYERS: usize = 4;
+
+/// How close a standing player has to get to a downed one to pick them back up.
+const REVIVE_DISTANCE: f32 = 48.0;
+/// Fraction of max health a revived player gets back.
+const REVIVE_HEALTH_FRACTION: f32 = 0.5;
+const REVIVE_INVULNERABILITY_SECONDS: f32 = 2.0;
+const SPAWN_SPACING: f32 = 48.0;
+
+const SLOT_COLORS: [Color; MAX_PLAYERS] = [
+    Color::srgb(0.0, 1.0, 0.0),
+    Color::srgb(0.3, 0.6, 1.0),
+    Color::srgb(1.0, 0.5, 0.2),
+    Color::srgb(0.9, 0.4, 1.0),
+];
+
+/// Local co-op setup for the next run, picked on the main menu.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+#[insert_resource(plugin = PlayerPlugin)]
+pub struct CoopSettings {
+    /// `1..=MAX_PLAYERS`, everyone after the first player needs a gamepad.
+    pub player_count: usize,
+    /// Experience picked up by anyone goes to everyone, otherwise each player levels on
+    /// their own.
+    pub shared_experience: bool,
+}
+
+impl Default for CoopSettings {
+    fn default() -> Self {
+        CoopSettings {
+            player_count: 1,
+            shared_experience: true,
+        }
+    }
+}
+
+impl CoopSettings {
+    pub fn controls_for(&self, slot: PlayerSlot) -> PlayerControls {
+        match slot.0 {
+            _ if self.player_count <= 1 => PlayerControls::Any,
+            0 => PlayerControls::Keyboard,
+            _ => PlayerControls::Gamepad(None),
+        }
+    }
+}
+
+/// Which local player this is, `0` is player one.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component)]
+pub struct PlayerSlot(pub usize);
+
+impl PlayerSlot {
+    /// One-based, for display.
+    pub fn number(&self) -> usize {
+        self.0 + 1
+    }
+
+    pub fn color(&self) -> Color {
+        SLOT_COLORS[self.0 % MAX_PLAYERS]
+    }
+
+    pub fn spawn_position(&self) -> Vec3 {
+        Vec3::new(self.0 as f32 * SPAWN_SPACING, 0.0, 0.0)
+    }
+}
+
+/// Out of health but waiting for another player to come and revive them. Downed
+/// players can't move, their weapons are switched off and enemies ignore them.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Downed;
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing))]
+fn down_players(
+    mut commands: Commands,
+    query: Query<(Entity, &PlayerSlot, &Health, &EquippedWeapons, Has<Downed>), With<Player>>,
+    children_query: Query<&Children>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    for (entity, slot, health, equipped, downed) in query.iter() {
+        if downed || health.current > 0.0 {
+            continue;
+        }
+
+        info!("Player {} is down", slot.number());
+        commands.entity(entity).insert(Downed);
+        for weapon in weapon_subtrees(equipped, &children_query) {
+            commands.entity(weapon).try_insert(Disabled);
+        }
+    }
+
+    // Nobody left standing to revive the others
+    if query
+        .iter()
+        .all(|(_, _, health, _, downed)| downed || health.current <= 0.0)
+    {
+        info!("Every player is down! Game Over!");
+        next_state.set(GameState::GameOver);
+    }
+}
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing), after = down_players)]
+fn revive_on_touch(
+    mut commands: Commands,
+    mut downed_query: Query<
+        (
+            Entity,
+            &PlayerSlot,
+            &GlobalTransform,
+            &EquippedWeapons,
+            &mut Health,
+            &mut Invulnerable,
+        ),
+        (With<Player>, With<Downed>),
+    >,
+    rescuer_query: Query<&GlobalTransform, (With<Player>, Without<Downed>)>,
+    children_query: Query<&Children, Or<(With<Disabled>, Without<Disabled>)>>,
+) {
+    for (entity, slot, transform, equipped, mut health, mut invulnerable) in downed_query.iter_mut()
+    {
+        let position = transform.translation().truncate();
+        let touched = rescuer_query.iter().any(|rescuer| {
+            rescuer.translation().truncate().distance_squared(position)
+                <= REVIVE_DISTANCE * REVIVE_DISTANCE
+        });
+        if !touched {
+            continue;
+        }
+
+        info!("Player {} was revived", slot.number());
+        health.current = health.max * REVIVE_HEALTH_FRACTION;
+        invulnerable.grant(REVIVE_INVULNERABILITY_SECONDS);
+        commands.entity(entity).remove::<Downed>();
+        for weapon in weapon_subtrees(equipped, &children_query) {
+            commands.entity(weapon).try_remove::<Disabled>();
+        }
+    }
+}
+
+/// Every equipped weapon along with its satellites, which would otherwise keep hitting
+/// while the weapon itself is disabled.
+fn weapon_subtrees<F: QueryFilter>(
+    equipped: &EquippedWeapons,
+    children_query: &Query<&Children, F>,
+) -> Vec<Entity> {
+    equipped
+        .0
+        .iter()
+        .flat_map(|weapon| {
+            std::iter::once(*weapon).chain(children_query.iter_descendants::<Children>(*weapon))
+        })
+        .collect()
+}
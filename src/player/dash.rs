@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use crate::actor::*;
+use crate::camera::CameraMode;
+use crate::input::*;
+use crate::player::*;
+
+const DASH_SPEED: f32 = 1400.0;
+const DASH_SECONDS: f32 = 0.15;
+/// I-frames granted by a dash, slightly longer than the burst itself.
+const DASH_INVULNERABILITY_SECONDS: f32 = 0.25;
+/// Time to get one charge back.
+const DASH_COOLDOWN_SECONDS: f32 = 1.2;
+
+/// Short burst of speed in the move direction. Charges come back one at a time, the
+/// maximum comes from [`Attribute::DashCharges`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Dash {
+    pub charges: u32,
+    pub max_charges: u32,
+    pub direction: Vec2,
+    pub burst: Timer,
+    pub cooldown: Timer,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        let mut burst = Timer::from_seconds(DASH_SECONDS, TimerMode::Once);
+        burst.tick(burst.duration());
+        Dash {
+            charges: 1,
+            max_charges: 1,
+            direction: Vec2::X,
+            burst,
+            cooldown: Timer::from_seconds(DASH_COOLDOWN_SECONDS, TimerMode::Repeating),
+        }
+    }
+}
+
+impl Dash {
+    pub fn is_dashing(&self) -> bool {
+        !self.burst.finished()
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        self.burst.tick(delta);
+
+        if self.charges < self.max_charges {
+            let recovered = self.cooldown.tick(delta).times_finished_this_tick();
+            self.charges = (self.charges + recovered).min(self.max_charges);
+        }
+        if self.charges >= self.max_charges {
+            self.cooldown.reset();
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing))]
+fn apply_dash_charges(mut query: Query<(&Attributes, &mut Dash), Changed<Attributes>>) {
+    for (attributes, mut dash) in query.iter_mut() {
+        let max_charges = attributes.get(Attribute::DashCharges).floor().max(0.0) as u32;
+        if max_charges != dash.max_charges {
+            // New charges arrive ready to use
+            let gained = max_charges.saturating_sub(dash.max_charges);
+            dash.max_charges = max_charges;
+            dash.charges = (dash.charges + gained).min(max_charges);
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing), run_if = not(resource_equals(CameraMode::Free)))]
+fn start_dash(
+    mut query: Query<
+        (
+            &ActionState<Action>,
+            &Facing,
+            &StatusEffects,
+            &mut Dash,
+            &mut Invulnerable,
+        ),
+        Without<Downed>,
+    >,
+) {
+    for (action_state, facing, effects, mut dash, mut invulnerable) in query.iter_mut() {
+        if !action_state.just_pressed(&Action::Dash)
+            || dash.charges == 0
+            || dash.is_dashing()
+            || effects.suppresses_ai()
+        {
+            continue;
+        }
+
+        let movement = action_state.clamped_axis_pair(&Action::Move);
+        dash.direction = if movement != Vec2::ZERO {
+            movement.normalize()
+        } else {
+            facing.0
+        };
+        dash.charges -= 1;
+        dash.burst.reset();
+        invulnerable.grant(DASH_INVULNERABILITY_SECONDS);
+    }
+}
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing), after = start_dash)]
+fn update_dash(time: Res<Time>, mut query: Query<(&mut Transform, &mut Dash)>) {
+    for (mut transform, mut dash) in query.iter_mut() {
+        if dash.is_dashing() {
+            // Straight line for now, once terrain has passability this needs to stop at
+            // the first blocked tile rather than going through it
+            let step = dash.direction * DASH_SPEED * time.delta_secs();
+            transform.translation += step.extend(0.0);
+        }
+
+        dash.tick(time.delta());
+    }
+}
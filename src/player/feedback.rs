@@ -0,0 +1,102 @@
+use crate::actor::*;
+use crate::camera::*;
+use crate::player::*;
+use crate::settings::Settings;
+
+/// Seconds between the player sprite blinking on and off while invulnerable.
+const FLASH_INTERVAL: f32 = 0.08;
+/// How long the vignette takes to fade out after a hit.
+const VIGNETTE_FADE_SECONDS: f32 = 0.4;
+const VIGNETTE_COLOR: Color = Color::srgb(0.8, 0.0, 0.0);
+const HIT_TRAUMA: f32 = 0.45;
+/// A brief freeze when the player is hit, shorter than an elite kill's.
+const HIT_STOP: HitStop = HitStop {
+    duration: 0.04,
+    time_scale: 0.1,
+};
+const DOWNED_ALPHA: f32 = 0.35;
+
+/// Red border drawn around the screen when the player is hit, `0.0` is invisible.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+struct DamageVignette(f32);
+
+#[add_system(schedule = OnEnter(GameState::InGame), plugin = PlayerPlugin)]
+fn spawn_damage_vignette(mut commands: Commands) {
+    commands.spawn((
+        DamageVignette::default(),
+        Name::new("Damage Vignette"),
+        StateScoped(GameState::InGame),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            border: UiRect::all(Val::Px(64.0)),
+            ..default()
+        },
+        BorderColor(VIGNETTE_COLOR.with_alpha(0.0)),
+        BackgroundColor(VIGNETTE_COLOR.with_alpha(0.0)),
+        Pickable::IGNORE,
+    ));
+}
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing))]
+fn react_to_player_hits(
+    mut damaged: EventReader<Damaged>,
+    player_query: Query<(), With<Player>>,
+    settings: Res<Settings>,
+    mut vignette_query: Query<&mut DamageVignette>,
+    mut shake: EventWriter<ShakeCamera>,
+    mut hit_stop: EventWriter<HitStop>,
+) {
+    let hit = damaged
+        .read()
+        .any(|event| !event.over_time && player_query.contains(event.target));
+    if !hit {
+        return;
+    }
+
+    if settings.damage_vignette {
+        for mut vignette in vignette_query.iter_mut() {
+            vignette.0 = 1.0;
+        }
+    }
+
+    shake.write(ShakeCamera(HIT_TRAUMA));
+    hit_stop.write(HIT_STOP);
+}
+
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing), after = react_to_player_hits)]
+fn fade_damage_vignette(
+    time: Res<Time>,
+    mut query: Query<(&mut DamageVignette, &mut BorderColor, &mut BackgroundColor)>,
+) {
+    for (mut vignette, mut border, mut background) in query.iter_mut() {
+        if vignette.0 <= 0.0 && border.0.alpha() <= 0.0 {
+            continue;
+        }
+
+        vignette.0 = (vignette.0 - time.delta_secs() / VIGNETTE_FADE_SECONDS).max(0.0);
+        border.0 = VIGNETTE_COLOR.with_alpha(vignette.0 * 0.6);
+        background.0 = VIGNETTE_COLOR.with_alpha(vignette.0 * 0.15);
+    }
+}
+
+/// Blinks the player while their i-frames are running, and fades them out while downed.
+#[add_system(schedule = Update, plugin = PlayerPlugin, run_if = in_state(InGameState::Playing))]
+fn flash_invulnerable_players(
+    mut query: Query<(&Invulnerable, Has<Downed>, &mut Sprite), With<Player>>,
+) {
+    for (invulnerable, downed, mut sprite) in query.iter_mut() {
+        let visible = !invulnerable.is_active()
+            || (invulnerable.elapsed_secs() / FLASH_INTERVAL) as u32 % 2 == 1;
+        let alpha = match (downed, visible) {
+            (true, _) => DOWNED_ALPHA,
+            (false, true) => 1.0,
+            (false, false) => 0.2,
+        };
+        if sprite.color.alpha() != alpha {
+            sprite.color.set_alpha(alpha);
+        }
+    }
+}
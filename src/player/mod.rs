@@ -1,20 +1,88 @@
 use crate::actor::*;
 use crate::camera::*;
 use crate::input::*;
+use crate::items::{PassiveItems, StatModifiers};
 use crate::prelude::*;
+use crate::progression::PendingLevelUps;
 use crate::weapon::components::*;
 
 #[derive(Component, Reflect, Debug, Default)]
 #[reflect(Component)]
 pub struct Experience(pub f32);
 
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Level(pub u32);
+
+impl Default for Level {
+    fn default() -> Self {
+        Level(1)
+    }
+}
+
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Gold(pub u32);
+
+/// Distance at which pickups start being pulled towards the player.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct PickupRadius(pub f32);
+
+impl Default for PickupRadius {
+    fn default() -> Self {
+        PickupRadius(96.0)
+    }
+}
+
+/// How long the player can't be hurt again after taking a hit.
+pub const PLAYER_INVULNERABILITY_SECONDS: f32 = 0.75;
+
 #[derive(Component, Reflect, Debug, Default)]
 #[reflect(Component)]
-#[require(Actor, Experience, InputMap<Action>, EquippedWeapons, CameraFocus)]
+#[require(Actor, PlayerSlot, PlayerControls, Experience, Level, PendingLevelUps, Gold, PickupRadius, InputMap<Action>, EquippedWeapons, PassiveItems, StatModifiers, CameraFocus, Dash, Invulnerable::new(PLAYER_INVULNERABILITY_SECONDS))]
 pub struct Player;
 
+impl Player {
+    /// Spawns a player with their starting weapon, scoped to the current run.
+    pub fn spawn(commands: &mut Commands, slot: PlayerSlot, controls: PlayerControls) -> Entity {
+        let weapon_entity = WeaponKind::OrbitAndLunge.spawn(commands, 1);
+
+        commands
+            .spawn((
+                Player,
+                slot,
+                controls,
+                Name::new(format!("Player {}", slot.number())),
+                Transform::from_translation(slot.spawn_position()),
+                StateScoped(GameState::InGame),
+                TeamFlag(Team::Player),
+                Sprite {
+                    color: Color::WHITE,
+                    custom_size: Some(Vec2::new(32.0, 32.0)),
+                    ..default()
+                },
+                Action::default_input_map(),
+                EquippedWeapons(vec![weapon_entity]),
+                ShowAabbGizmo {
+                    color: Some(slot.color()),
+                },
+                Attributes::default().with_base(Attribute::MoveSpeed, 256.0),
+            ))
+            .add_child(weapon_entity)
+            .id()
+    }
+}
+
 #[butler_plugin]
 #[add_plugin(to_group = EntityPlugins)]
 struct PlayerPlugin;
 
+mod coop;
+pub(crate) use coop::*;
+
+mod dash;
+pub(crate) use dash::*;
+
+mod feedback;
 mod systems;
@@ -0,0 +1,107 @@
+use bevy::platform::collections::HashSet;
+
+use crate::prelude::*;
+
+mod upgrade;
+pub(crate) use upgrade::*;
+
+/// Experience needed per level grows linearly: `base + growth * (level - 1)`.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+#[insert_resource(plugin = ProgressionPlugin)]
+pub struct XpCurve {
+    pub base: f32,
+    pub growth: f32,
+    pub max_level: u32,
+}
+
+impl Default for XpCurve {
+    fn default() -> Self {
+        XpCurve {
+            base: 5.0,
+            growth: 10.0,
+            max_level: 400,
+        }
+    }
+}
+
+impl XpCurve {
+    pub fn xp_to_next(&self, level: u32) -> f32 {
+        self.base + self.growth * level.saturating_sub(1) as f32
+    }
+
+    /// Returns the level reached with `experience` total experience, along with the
+    /// experience gained into that level and the experience needed to finish it.
+    pub fn level_progress(&self, experience: f32) -> (u32, f32, f32) {
+        let mut level = 1;
+        let mut remaining = experience;
+
+        while level < self.max_level {
+            let needed = self.xp_to_next(level);
+            if remaining < needed {
+                return (level, remaining, needed);
+            }
+
+            remaining -= needed;
+            level += 1;
+        }
+
+        (self.max_level, 0.0, 0.0)
+    }
+
+    pub fn level_for(&self, experience: f32) -> u32 {
+        self.level_progress(experience).0
+    }
+}
+
+/// Levels gained that haven't had an upgrade picked yet.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct PendingLevelUps(pub u32);
+
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = ProgressionPlugin)]
+pub struct LevelUpEvent {
+    pub player: Entity,
+    pub level: u32,
+}
+
+/// How many options are offered and how many rerolls, banishes and skips are left this run.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+#[insert_resource(plugin = ProgressionPlugin)]
+pub struct LevelUpCharges {
+    pub choice_count: usize,
+    pub rerolls: u32,
+    pub banishes: u32,
+    pub skips: u32,
+}
+
+impl Default for LevelUpCharges {
+    fn default() -> Self {
+        LevelUpCharges {
+            choice_count: 3,
+            rerolls: 2,
+            banishes: 1,
+            skips: 1,
+        }
+    }
+}
+
+/// Upgrades that will never be offered again this run.
+#[derive(Resource, Debug, Default)]
+#[insert_resource(plugin = ProgressionPlugin)]
+pub struct BanishedUpgrades(pub HashSet<Upgrade>);
+
+/// The options currently on screen.
+#[derive(Resource, Debug)]
+pub struct LevelUpChoices {
+    pub player: Entity,
+    pub options: Vec<Upgrade>,
+}
+
+#[butler_plugin]
+#[add_plugin(to_group = EntityPlugins)]
+struct ProgressionPlugin;
+
+mod systems;
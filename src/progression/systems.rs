@@ -0,0 +1,314 @@
+use crate::actor::*;
+use crate::camera::FlashScreen;
+use crate::input::MenuAction;
+use crate::items::*;
+use crate::player::*;
+use crate::progression::*;
+use crate::ui::*;
+use crate::weapon::components::*;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct LevelUpScreen;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct UpgradeOption(usize);
+
+const LEVEL_UP_FLASH: FlashScreen = FlashScreen {
+    color: Color::srgba(1.0, 0.85, 0.3, 0.35),
+    duration: 0.3,
+};
+
+#[add_system(schedule = Update, plugin = ProgressionPlugin, run_if = in_state(InGameState::Playing))]
+fn track_experience(
+    mut query: Query<
+        (Entity, &Experience, &mut Level, &mut PendingLevelUps),
+        (With<Player>, Changed<Experience>),
+    >,
+    curve: Res<XpCurve>,
+    mut level_ups: EventWriter<LevelUpEvent>,
+    mut flash: EventWriter<FlashScreen>,
+) {
+    for (entity, experience, mut level, mut pending) in query.iter_mut() {
+        let new_level = curve.level_for(experience.0);
+        while level.0 < new_level {
+            level.0 += 1;
+            pending.0 += 1;
+
+            info!("Player {} reached level {}", entity, level.0);
+            level_ups.write(LevelUpEvent {
+                player: entity,
+                level: level.0,
+            });
+            flash.write(LEVEL_UP_FLASH);
+        }
+    }
+}
+
+fn equipped_weapon_levels(
+    equipped: &EquippedWeapons,
+    lookup: impl Fn(Entity) -> Option<(WeaponKind, u32)>,
+) -> Vec<(WeaponKind, u32)> {
+    equipped
+        .0
+        .iter()
+        .filter_map(|entity| lookup(*entity))
+        .collect()
+}
+
+#[add_system(schedule = Update, plugin = ProgressionPlugin, run_if = in_state(InGameState::Playing), after = track_experience)]
+fn open_level_up(
+    mut commands: Commands,
+    player_query: Query<(Entity, &PendingLevelUps, &EquippedWeapons, &PassiveItems), With<Player>>,
+    weapon_query: Query<(&WeaponKind, &WeaponLevel)>,
+    passive_definitions: PassiveItemDefinitions,
+    charges: Res<LevelUpCharges>,
+    banished: Res<BanishedUpgrades>,
+    mut next_state: ResMut<NextState<InGameState>>,
+) {
+    let Some((player, _, equipped, passives)) =
+        player_query.iter().find(|(_, pending, ..)| pending.0 > 0)
+    else {
+        return;
+    };
+
+    let options = roll_upgrades(
+        &equipped_weapon_levels(equipped, |entity| {
+            weapon_query
+                .get(entity)
+                .ok()
+                .map(|(kind, level)| (*kind, level.0))
+        }),
+        passives,
+        |item| passive_definitions.max_level(item),
+        &banished.0,
+        charges.choice_count,
+    );
+
+    commands.insert_resource(LevelUpChoices { player, options });
+    next_state.set(InGameState::LevelUp);
+}
+
+#[add_system(schedule = OnExit(InGameState::LevelUp), plugin = ProgressionPlugin)]
+fn despawn_level_up_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<LevelUpScreen>>,
+) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[add_system(
+    schedule = Update,
+    plugin = ProgressionPlugin,
+    run_if = in_state(InGameState::LevelUp),
+    run_if = resource_exists_and_changed::<LevelUpChoices>
+)]
+fn draw_level_up_screen(
+    mut commands: Commands,
+    choices: Res<LevelUpChoices>,
+    charges: Res<LevelUpCharges>,
+    level_query: Query<(&Level, &PlayerSlot)>,
+    coop: Res<CoopSettings>,
+    screen_query: Query<Entity, With<LevelUpScreen>>,
+) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let (level, slot) = level_query
+        .get(choices.player)
+        .map(|(level, slot)| (level.0, *slot))
+        .unwrap_or((1, PlayerSlot(0)));
+    // Say whose pick it is when several players level up together
+    let heading = if coop.player_count > 1 {
+        format!("Player {} - Level {}!", slot.number(), level)
+    } else {
+        format!("Level {}!", level)
+    };
+
+    commands
+        .spawn((LevelUpScreen, Name::new("Level Up Screen"), overlay()))
+        .with_children(|parent| {
+            parent.spawn(title(heading));
+
+            if choices.options.is_empty() {
+                parent.spawn(label("Nothing left to upgrade"));
+                parent.spawn(menu_button("Continue", 0));
+            }
+
+            for (index, option) in choices.options.iter().enumerate() {
+                parent.spawn((UpgradeOption(index), menu_button(option.label(), index)));
+            }
+
+            parent.spawn(label(format!(
+                "[R] Reroll ({})   [B] Banish ({})   [X] Skip ({})",
+                charges.rerolls, charges.banishes, charges.skips
+            )));
+        });
+}
+
+fn apply_upgrade(
+    upgrade: Upgrade,
+    player: Entity,
+    commands: &mut Commands,
+    player_query: &mut Query<
+        (&mut EquippedWeapons, &mut Attributes, &mut PassiveItems),
+        With<Player>,
+    >,
+    weapon_query: &mut Query<(&WeaponKind, &mut WeaponLevel)>,
+) {
+    let Ok((mut equipped, mut attributes, mut passives)) = player_query.get_mut(player) else {
+        return;
+    };
+
+    info!("Applying upgrade {:?} to {}", upgrade, player);
+
+    match upgrade {
+        Upgrade::NewWeapon(kind) => {
+            let weapon_entity = kind.spawn(commands, 1);
+            commands.entity(player).add_child(weapon_entity);
+            equipped.0.push(weapon_entity);
+        }
+        Upgrade::WeaponLevel(kind) => {
+            for weapon_entity in equipped.0.iter() {
+                let Ok((weapon_kind, mut level)) = weapon_query.get_mut(*weapon_entity) else {
+                    continue;
+                };
+
+                // Each weapon type reacts to its level changing
+                if *weapon_kind == kind {
+                    level.0 += 1;
+                }
+            }
+        }
+        Upgrade::NewPassive(item) => passives.0.push((item, 1)),
+        Upgrade::PassiveLevel(item) => {
+            for (held, level) in passives.0.iter_mut() {
+                if *held == item {
+                    *level += 1;
+                }
+            }
+        }
+        Upgrade::Stat(StatUpgrade::MaxHealth) => attributes.add_modifier(
+            Attribute::MaxHealth,
+            AttributeModifier::flat(ModifierSource::Upgrade, 20.0),
+        ),
+        Upgrade::Stat(StatUpgrade::MoveSpeed) => attributes.add_modifier(
+            Attribute::MoveSpeed,
+            AttributeModifier::percent_multiply(ModifierSource::Upgrade, 0.1),
+        ),
+        Upgrade::Stat(StatUpgrade::PickupRadius) => attributes.add_modifier(
+            Attribute::PickupRadius,
+            AttributeModifier::percent_multiply(ModifierSource::Upgrade, 0.25),
+        ),
+        Upgrade::Stat(StatUpgrade::DashCharge) => attributes.add_modifier(
+            Attribute::DashCharges,
+            AttributeModifier::flat(ModifierSource::Upgrade, 1.0),
+        ),
+    }
+}
+
+#[add_system(schedule = Update, plugin = ProgressionPlugin, run_if = in_state(InGameState::LevelUp))]
+fn handle_level_up_input(
+    mut commands: Commands,
+    menu_actions: Res<ActionState<MenuAction>>,
+    mut pressed: EventReader<MenuButtonPressed>,
+    option_query: Query<(&UpgradeOption, &MenuButton)>,
+    mut choices: ResMut<LevelUpChoices>,
+    mut charges: ResMut<LevelUpCharges>,
+    mut banished: ResMut<BanishedUpgrades>,
+    mut next_state: ResMut<NextState<InGameState>>,
+    mut pending_query: Query<&mut PendingLevelUps>,
+    mut player_query: Query<
+        (&mut EquippedWeapons, &mut Attributes, &mut PassiveItems),
+        With<Player>,
+    >,
+    mut weapon_query: Query<(&WeaponKind, &mut WeaponLevel)>,
+    passive_definitions: PassiveItemDefinitions,
+) {
+    let player = choices.player;
+    let selected = option_query
+        .iter()
+        .find(|(_, button)| button.selected)
+        .map(|(option, _)| option.0);
+
+    let mut reroll = false;
+
+    if menu_actions.just_pressed(&MenuAction::Reroll) && charges.rerolls > 0 {
+        charges.rerolls -= 1;
+        reroll = true;
+    }
+
+    let banish = menu_actions.just_pressed(&MenuAction::Banish) && charges.banishes > 0;
+    if let Some(upgrade) = selected
+        .and_then(|index| choices.options.get(index).copied())
+        .filter(|_| banish)
+    {
+        charges.banishes -= 1;
+        banished.0.insert(upgrade);
+        reroll = true;
+    }
+
+    if reroll {
+        if let Ok((equipped, _, passives)) = player_query.get(player) {
+            let weapons = equipped_weapon_levels(equipped, |entity| {
+                weapon_query
+                    .get(entity)
+                    .ok()
+                    .map(|(kind, level)| (*kind, level.0))
+            });
+
+            choices.options = roll_upgrades(
+                &weapons,
+                passives,
+                |item| passive_definitions.max_level(item),
+                &banished.0,
+                charges.choice_count,
+            );
+        }
+        pressed.clear();
+        return;
+    }
+
+    let skipped = menu_actions.just_pressed(&MenuAction::Skip) && charges.skips > 0;
+    if skipped {
+        charges.skips -= 1;
+    }
+
+    let pressed_button = pressed.read().last().map(|event| event.0);
+    if pressed_button.is_none() && !skipped {
+        return;
+    }
+
+    // With nothing left to offer the only button is "Continue", which has no option attached
+    let picked = pressed_button
+        .filter(|_| !skipped)
+        .and_then(|entity| option_query.get(entity).ok())
+        .and_then(|(option, _)| choices.options.get(option.0).copied());
+
+    if let Some(upgrade) = picked {
+        apply_upgrade(
+            upgrade,
+            player,
+            &mut commands,
+            &mut player_query,
+            &mut weapon_query,
+        );
+    }
+
+    if let Ok(mut pending) = pending_query.get_mut(player) {
+        pending.0 = pending.0.saturating_sub(1);
+    }
+
+    next_state.set(InGameState::Playing);
+}
+
+#[add_system(schedule = OnExit(GameState::InGame), plugin = ProgressionPlugin)]
+fn reset_progression_resources(mut commands: Commands) {
+    commands.insert_resource(LevelUpCharges::default());
+    commands.insert_resource(BanishedUpgrades::default());
+    commands.remove_resource::<LevelUpChoices>();
+}
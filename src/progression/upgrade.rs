@@ -0,0 +1,96 @@
+use bevy::platform::collections::HashSet;
+use rand::seq::SliceRandom;
+
+use crate::items::*;
+use crate::prelude::*;
+use crate::weapon::components::*;
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatUpgrade {
+    MaxHealth,
+    MoveSpeed,
+    PickupRadius,
+    DashCharge,
+}
+
+impl StatUpgrade {
+    pub const ALL: [StatUpgrade; 4] = [
+        StatUpgrade::MaxHealth,
+        StatUpgrade::MoveSpeed,
+        StatUpgrade::PickupRadius,
+        StatUpgrade::DashCharge,
+    ];
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Upgrade {
+    NewWeapon(WeaponKind),
+    WeaponLevel(WeaponKind),
+    NewPassive(PassiveItem),
+    PassiveLevel(PassiveItem),
+    Stat(StatUpgrade),
+}
+
+impl Upgrade {
+    pub fn label(&self) -> String {
+        match self {
+            Upgrade::NewWeapon(kind) => format!("New weapon: {}", kind.name()),
+            Upgrade::WeaponLevel(kind) => format!("Level up {}", kind.name()),
+            Upgrade::NewPassive(item) => format!("New passive: {}", item.name()),
+            Upgrade::PassiveLevel(item) => format!("Level up {}", item.name()),
+            Upgrade::Stat(StatUpgrade::MaxHealth) => "+20 Max Health".to_string(),
+            Upgrade::Stat(StatUpgrade::MoveSpeed) => "+10% Move Speed".to_string(),
+            Upgrade::Stat(StatUpgrade::PickupRadius) => "+25% Pickup Radius".to_string(),
+            Upgrade::Stat(StatUpgrade::DashCharge) => "+1 Dash Charge".to_string(),
+        }
+    }
+}
+
+/// Picks up to `count` distinct upgrades the player is currently eligible for.
+pub fn roll_upgrades(
+    equipped: &[(WeaponKind, u32)],
+    passives: &PassiveItems,
+    passive_max_level: impl Fn(PassiveItem) -> u32,
+    banished: &HashSet<Upgrade>,
+    count: usize,
+) -> Vec<Upgrade> {
+    let mut pool = Vec::new();
+
+    if equipped.len() < MAX_EQUIPPED_WEAPONS {
+        for kind in WeaponKind::ALL {
+            if !equipped
+                .iter()
+                .any(|(equipped_kind, _)| *equipped_kind == kind)
+            {
+                pool.push(Upgrade::NewWeapon(kind));
+            }
+        }
+    }
+
+    for (kind, level) in equipped {
+        if *level < MAX_WEAPON_LEVEL {
+            pool.push(Upgrade::WeaponLevel(*kind));
+        }
+    }
+
+    for item in PassiveItem::ALL {
+        match passives.level(item) {
+            Some(level) if level < passive_max_level(item) => {
+                pool.push(Upgrade::PassiveLevel(item));
+            }
+            None if passives.0.len() < MAX_PASSIVE_ITEMS && passive_max_level(item) > 0 => {
+                pool.push(Upgrade::NewPassive(item));
+            }
+            _ => {}
+        }
+    }
+
+    for stat in StatUpgrade::ALL {
+        pool.push(Upgrade::Stat(stat));
+    }
+
+    pool.retain(|upgrade| !banished.contains(upgrade));
+    pool.shuffle(&mut rand::thread_rng());
+    pool.truncate(count);
+    pool
+}
@@ -0,0 +1,167 @@
+use crate::input::KeyBindings;
+use crate::player::{CoopSettings, MAX_PLAYERS};
+use crate::run::*;
+use crate::settings::*;
+use crate::ui::*;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct MainMenu;
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum RunMenuButton {
+    StartRun,
+    CyclePlayers,
+    ToggleSharedExperience,
+    Settings,
+    Restart,
+    MainMenu,
+    Quit,
+}
+
+pub fn format_run_time(secs: f32) -> String {
+    let total = secs as u32;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
+
+/// Spawned on entering the main menu and rebuilt whenever the co-op setup changes so
+/// the labels stay current.
+#[add_system(
+    schedule = Update,
+    plugin = RunPlugin,
+    run_if = in_state(MainMenuState::Main),
+    run_if = resource_changed::<CoopSettings>.or(not(any_with_component::<MainMenu>))
+)]
+fn spawn_main_menu(
+    mut commands: Commands,
+    coop: Res<CoopSettings>,
+    menu_query: Query<Entity, With<MainMenu>>,
+) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let experience = if coop.shared_experience {
+        "Shared"
+    } else {
+        "Per player"
+    };
+
+    let menu = commands
+        .spawn((
+            MainMenu,
+            Name::new("Main Menu"),
+            StateScoped(GameState::MainMenu),
+            overlay(),
+            children![
+                title("Shadows Over Ebonshire"),
+                (RunMenuButton::StartRun, menu_button("Start Run", 0)),
+                (
+                    RunMenuButton::CyclePlayers,
+                    menu_button(format!("Players: {}", coop.player_count), 1)
+                ),
+                (
+                    RunMenuButton::ToggleSharedExperience,
+                    menu_button(format!("Experience: {}", experience), 2)
+                ),
+                (RunMenuButton::Settings, menu_button("Settings", 3)),
+                (RunMenuButton::Quit, menu_button("Quit", 4)),
+            ],
+        ))
+        .id();
+
+    if coop.player_count > 1 {
+        commands.entity(menu).with_child(label(format!(
+            "Player 1 uses the keyboard, players 2-{} a gamepad each, in the order they connect",
+            coop.player_count
+        )));
+    }
+}
+
+#[add_system(schedule = OnExit(MainMenuState::Main), plugin = RunPlugin)]
+fn despawn_main_menu(mut commands: Commands, query: Query<Entity, With<MainMenu>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[add_system(schedule = OnEnter(MainMenuState::Settings), plugin = RunPlugin)]
+fn open_main_menu_settings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    bindings: Res<KeyBindings>,
+    awaiting: Res<AwaitingBinding>,
+) {
+    spawn_settings_screen(&mut commands, &settings, &bindings, &awaiting);
+}
+
+#[add_system(schedule = OnExit(MainMenuState::Settings), plugin = RunPlugin)]
+fn close_main_menu_settings(mut commands: Commands, query: Query<Entity, With<SettingsScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[add_system(schedule = Update, plugin = RunPlugin, run_if = in_state(MainMenuState::Settings))]
+fn close_settings_to_main_menu(
+    mut settings_closed: EventReader<SettingsClosed>,
+    mut next_state: ResMut<NextState<MainMenuState>>,
+) {
+    if settings_closed.read().count() > 0 {
+        next_state.set(MainMenuState::Main);
+    }
+}
+
+#[add_system(schedule = OnEnter(GameState::GameOver), plugin = RunPlugin)]
+fn spawn_game_over_screen(mut commands: Commands, stats: Res<RunStats>) {
+    commands.spawn((
+        Name::new("Game Over Screen"),
+        StateScoped(GameState::GameOver),
+        overlay(),
+        children![
+            title("Game Over"),
+            label(format!(
+                "Survived {}   Level {}   Kills {}",
+                format_run_time(stats.elapsed_secs),
+                stats.level,
+                stats.kills
+            )),
+            (RunMenuButton::Restart, menu_button("Restart", 0)),
+            (RunMenuButton::MainMenu, menu_button("Main Menu", 1)),
+        ],
+    ));
+}
+
+#[add_system(schedule = Update, plugin = RunPlugin, run_if = in_state(GameState::MainMenu).or(in_state(GameState::GameOver)))]
+fn handle_run_menu_buttons(
+    mut pressed: EventReader<MenuButtonPressed>,
+    button_query: Query<&RunMenuButton>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_main_menu_state: ResMut<NextState<MainMenuState>>,
+    mut coop: ResMut<CoopSettings>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for event in pressed.read() {
+        let Ok(button) = button_query.get(event.0) else {
+            continue;
+        };
+
+        match button {
+            RunMenuButton::StartRun | RunMenuButton::Restart => {
+                next_state.set(GameState::InGame);
+            }
+            RunMenuButton::CyclePlayers => {
+                coop.player_count = coop.player_count % MAX_PLAYERS + 1;
+            }
+            RunMenuButton::ToggleSharedExperience => {
+                coop.shared_experience = !coop.shared_experience;
+            }
+            RunMenuButton::Settings => next_main_menu_state.set(MainMenuState::Settings),
+            RunMenuButton::MainMenu => next_state.set(GameState::MainMenu),
+            RunMenuButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::prelude::*;
+
+/// Stats about the current (or last finished) run.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+#[insert_resource(plugin = RunPlugin)]
+pub struct RunStats {
+    pub elapsed_secs: f32,
+    pub kills: u32,
+    pub level: u32,
+}
+
+#[butler_plugin]
+#[add_plugin(to_group = CorePlugins)]
+struct RunPlugin;
+
+mod menus;
+pub(crate) use menus::format_run_time;
+
+mod pause;
+mod systems;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy::window::WindowResized;
+
+    use crate::enemy::{EnemyArchetype, EnemyKdTree, EnemyRank, SlimeAssets};
+    use crate::input::MenuAction;
+    use crate::pickups::{Pickup, PickupKind};
+    use crate::player::Player;
+    use crate::prelude::*;
+    use crate::settings::Settings;
+    use crate::weapon::components::{EquippedWeapons, Projectile, WeaponKind};
+    use crate::weapon::definition::{WeaponAssets, WeaponCatalog};
+    use crate::world::systems::{ChunkManager, reset_chunk_manager};
+    use crate::world::{WorldGeneratationConfig, WorldGenerator};
+
+    use super::RunStats;
+
+    const FRAMES_PER_RUN: usize = 30;
+
+    /// Fills the run with what a few seconds of play would, on top of what the real
+    /// plugins spawn themselves.
+    fn populate_run(
+        mut commands: Commands,
+        slime_assets: Res<SlimeAssets>,
+        generator: Res<WorldGenerator>,
+        config: Res<WorldGeneratationConfig>,
+        mut chunks: ResMut<ChunkManager>,
+    ) {
+        for i in 0..16 {
+            let position = Vec3::new(200.0 + i as f32 * 32.0, 0.0, 0.0);
+            commands.spawn(EnemyArchetype::Slime.bundle(
+                EnemyRank::Normal,
+                &slime_assets,
+                position,
+            ));
+            commands.spawn(Pickup::bundle(PickupKind::Experience(1.0), position));
+        }
+
+        // The world plugin needs a renderer, so load the chunk the way it would
+        let chunk = generator.generate_chunk(&mut commands, 0, 0, &config, Handle::default());
+        chunks.0.insert((0, 0), chunk);
+    }
+
+    /// Gives every new player a projectile weapon next to their starting one.
+    fn arm_players(
+        mut commands: Commands,
+        mut query: Query<(Entity, &mut EquippedWeapons), Added<Player>>,
+    ) {
+        for (player, mut equipped) in query.iter_mut() {
+            let weapon = WeaponKind::RandomProjectile.spawn(&mut commands, 1);
+            commands.entity(player).add_child(weapon);
+            equipped.0.push(weapon);
+        }
+    }
+
+    fn weapon_assets(mut commands: Commands, mut catalogs: ResMut<Assets<WeaponCatalog>>) {
+        let catalog: WeaponCatalog = ron::from_str(include_str!("../../assets/data/weapons.ron"))
+            .expect("weapons.ron should parse");
+        commands.insert_resource(WeaponAssets {
+            catalog: catalogs.add(catalog),
+        });
+    }
+
+    fn set_state(app: &mut App, state: GameState) {
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(state);
+        app.update();
+    }
+
+    fn entity_count(app: &App) -> u32 {
+        app.world().entities().len()
+    }
+
+    fn resource_count(app: &App) -> usize {
+        app.world().iter_resources().count()
+    }
+
+    fn count<T: Component>(app: &mut App) -> usize {
+        app.world_mut()
+            .query_filtered::<(), With<T>>()
+            .iter(app.world())
+            .count()
+    }
+
+    /// Headless app running the real entity and rendering plugins, standing in for the
+    /// core plugins that need a window or a renderer.
+    fn headless_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            TransformPlugin,
+            bevy::input::InputPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .init_asset::<Image>()
+        .init_asset::<TextureAtlasLayout>()
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .add_event::<WindowResized>()
+        .init_resource::<UiScale>()
+        .init_resource::<ActionState<MenuAction>>()
+        .init_resource::<Settings>()
+        .init_resource::<RunStats>()
+        .init_resource::<WorldGeneratationConfig>()
+        .init_resource::<WorldGenerator>()
+        .init_resource::<ChunkManager>()
+        .insert_resource(SlimeAssets {
+            sprite: Handle::default(),
+            layout: Handle::default(),
+        })
+        .init_state::<GameState>()
+        .add_sub_state::<InGameState>()
+        .add_sub_state::<PauseMenuState>()
+        .enable_state_scoped_entities::<GameState>()
+        .add_plugins((EntityPlugins, RenderingPlugins))
+        .add_systems(Startup, weapon_assets)
+        .add_systems(OnEnter(GameState::InGame), populate_run)
+        .add_systems(OnExit(GameState::InGame), reset_chunk_manager)
+        .add_systems(Update, arm_players.run_if(in_state(GameState::InGame)));
+
+        app
+    }
+
+    #[test]
+    fn restarting_a_run_does_not_leak_entities() {
+        let mut app = headless_app();
+
+        set_state(&mut app, GameState::MainMenu);
+        let baseline_entities = entity_count(&app);
+        let baseline_resources = resource_count(&app);
+
+        for _ in 0..3 {
+            set_state(&mut app, GameState::InGame);
+            for _ in 0..FRAMES_PER_RUN {
+                app.update();
+            }
+
+            assert!(count::<Player>(&mut app) > 0);
+            assert!(count::<Projectile>(&mut app) > 0);
+            assert!(entity_count(&app) > baseline_entities);
+            assert!(!app.world().resource::<ChunkManager>().0.is_empty());
+
+            set_state(&mut app, GameState::GameOver);
+            assert_eq!(entity_count(&app), baseline_entities);
+            assert_eq!(resource_count(&app), baseline_resources);
+            assert!(app.world().resource::<EnemyKdTree>().0.is_empty());
+            assert!(app.world().resource::<ChunkManager>().0.is_empty());
+        }
+    }
+}
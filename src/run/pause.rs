@@ -0,0 +1,122 @@
+use bevy::window::WindowFocused;
+
+use crate::input::{KeyBindings, MenuAction};
+use crate::run::*;
+use crate::settings::*;
+use crate::ui::*;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PauseMenuScreen;
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum PauseMenuButton {
+    Resume,
+    Settings,
+    QuitRun,
+}
+
+#[add_system(schedule = Update, plugin = RunPlugin, run_if = in_state(GameState::InGame))]
+fn toggle_pause(
+    menu_actions: Res<ActionState<MenuAction>>,
+    in_game_state: Res<State<InGameState>>,
+    pause_menu_state: Option<Res<State<PauseMenuState>>>,
+    mut next_in_game_state: ResMut<NextState<InGameState>>,
+    mut next_pause_menu_state: ResMut<NextState<PauseMenuState>>,
+) {
+    if !menu_actions.just_pressed(&MenuAction::Pause) {
+        return;
+    }
+
+    match (
+        in_game_state.get(),
+        pause_menu_state.as_deref().map(State::get),
+    ) {
+        (InGameState::Playing, _) => next_in_game_state.set(InGameState::Paused),
+        (InGameState::Paused, Some(PauseMenuState::Settings)) => {
+            next_pause_menu_state.set(PauseMenuState::Main)
+        }
+        (InGameState::Paused, _) => next_in_game_state.set(InGameState::Playing),
+        // Level ups have to be resolved before the game can be paused
+        (InGameState::LevelUp, _) => {}
+    }
+}
+
+#[add_system(schedule = Update, plugin = RunPlugin, run_if = in_state(InGameState::Playing))]
+fn auto_pause_on_focus_loss(
+    mut focus_events: EventReader<WindowFocused>,
+    settings: Res<Settings>,
+    mut next_state: ResMut<NextState<InGameState>>,
+) {
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    if lost_focus && settings.auto_pause_on_focus_loss {
+        info!("Window lost focus, pausing");
+        next_state.set(InGameState::Paused);
+    }
+}
+
+#[add_system(schedule = OnEnter(PauseMenuState::Main), plugin = RunPlugin)]
+fn spawn_pause_menu(mut commands: Commands) {
+    commands.spawn((
+        PauseMenuScreen,
+        Name::new("Pause Menu"),
+        overlay(),
+        children![
+            title("Paused"),
+            (PauseMenuButton::Resume, menu_button("Resume", 0)),
+            (PauseMenuButton::Settings, menu_button("Settings", 1)),
+            (PauseMenuButton::QuitRun, menu_button("Quit Run", 2)),
+        ],
+    ));
+}
+
+#[add_system(schedule = OnExit(PauseMenuState::Main), plugin = RunPlugin)]
+fn despawn_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseMenuScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[add_system(schedule = OnEnter(PauseMenuState::Settings), plugin = RunPlugin)]
+fn open_pause_settings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    bindings: Res<KeyBindings>,
+    awaiting: Res<AwaitingBinding>,
+) {
+    spawn_settings_screen(&mut commands, &settings, &bindings, &awaiting);
+}
+
+#[add_system(schedule = OnExit(PauseMenuState::Settings), plugin = RunPlugin)]
+fn close_pause_settings(mut commands: Commands, query: Query<Entity, With<SettingsScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[add_system(schedule = Update, plugin = RunPlugin, run_if = in_state(InGameState::Paused))]
+fn handle_pause_menu_buttons(
+    mut pressed: EventReader<MenuButtonPressed>,
+    mut settings_closed: EventReader<SettingsClosed>,
+    button_query: Query<&PauseMenuButton>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_in_game_state: ResMut<NextState<InGameState>>,
+    mut next_pause_menu_state: ResMut<NextState<PauseMenuState>>,
+) {
+    if settings_closed.read().count() > 0 {
+        next_pause_menu_state.set(PauseMenuState::Main);
+    }
+
+    for event in pressed.read() {
+        let Ok(button) = button_query.get(event.0) else {
+            continue;
+        };
+
+        match button {
+            PauseMenuButton::Resume => next_in_game_state.set(InGameState::Playing),
+            PauseMenuButton::Settings => next_pause_menu_state.set(PauseMenuState::Settings),
+            PauseMenuButton::QuitRun => next_game_state.set(GameState::MainMenu),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::enemy::EnemyDied;
+use crate::progression::LevelUpEvent;
+use crate::run::*;
+
+#[add_system(schedule = OnEnter(GameState::InGame), plugin = RunPlugin)]
+fn start_run(mut stats: ResMut<RunStats>) {
+    info!("Starting new run");
+    *stats = RunStats {
+        level: 1,
+        ..default()
+    };
+}
+
+#[add_system(schedule = Update, plugin = RunPlugin, run_if = in_state(InGameState::Playing))]
+fn track_run_stats(
+    time: Res<Time>,
+    mut stats: ResMut<RunStats>,
+    mut died: EventReader<EnemyDied>,
+    mut level_ups: EventReader<LevelUpEvent>,
+) {
+    stats.elapsed_secs += time.delta_secs();
+    stats.kills += died.read().count() as u32;
+
+    if let Some(level) = level_ups.read().map(|event| event.level).max() {
+        stats.level = stats.level.max(level);
+    }
+}
+
+/// Freezes virtual time whenever the run isn't actively being played, so timers,
+/// animations and `on_timer` conditions don't advance behind menus.
+#[add_system(schedule = OnExit(InGameState::Playing), plugin = RunPlugin)]
+fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+#[add_system(schedule = OnEnter(InGameState::Playing), plugin = RunPlugin)]
+fn resume_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
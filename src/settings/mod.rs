@@ -0,0 +1,240 @@
+use crate::input::{Binding, KeyBindings};
+use crate::prelude::*;
+use crate::ui::*;
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+#[insert_resource(plugin = SettingsPlugin)]
+pub struct Settings {
+    pub auto_pause_on_focus_loss: bool,
+    /// Red edge flash when the player gets hit.
+    pub damage_vignette: bool,
+    /// Strength of camera shake, hit-stop and screen flashes, `0.0` turns them off.
+    pub effects_scale: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            auto_pause_on_focus_loss: true,
+            damage_vignette: true,
+            effects_scale: 1.0,
+        }
+    }
+}
+
+/// Root of the settings screen, whoever opens it is responsible for despawning it.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct SettingsScreen;
+
+/// Binding the settings screen is waiting on a key or button press for.
+#[derive(Resource, Debug, Default)]
+#[insert_resource(plugin = SettingsPlugin)]
+pub struct AwaitingBinding(pub Option<Binding>);
+
+/// Emitted when the player backs out of the settings screen.
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = SettingsPlugin)]
+pub struct SettingsClosed;
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum SettingsButton {
+    ToggleAutoPause,
+    ToggleDamageVignette,
+    CycleEffectsScale,
+    Rebind(Binding),
+    Back,
+}
+
+/// Steps the effects strength setting cycles through.
+const EFFECTS_SCALES: [f32; 3] = [1.0, 0.5, 0.0];
+
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
+fn percent_or_off(value: f32) -> String {
+    if value <= 0.0 {
+        "Off".to_string()
+    } else {
+        format!("{:.0}%", value * 100.0)
+    }
+}
+
+pub fn spawn_settings_screen(
+    commands: &mut Commands,
+    settings: &Settings,
+    bindings: &KeyBindings,
+    awaiting: &AwaitingBinding,
+) -> Entity {
+    commands
+        .spawn((
+            SettingsScreen,
+            Name::new("Settings Screen"),
+            overlay(),
+            children![
+                title("Settings"),
+                (
+                    SettingsButton::ToggleAutoPause,
+                    menu_button(
+                        format!(
+                            "Pause when unfocused: {}",
+                            on_off(settings.auto_pause_on_focus_loss)
+                        ),
+                        0
+                    ),
+                ),
+                (
+                    SettingsButton::ToggleDamageVignette,
+                    menu_button(
+                        format!("Damage vignette: {}", on_off(settings.damage_vignette)),
+                        1
+                    ),
+                ),
+                (
+                    SettingsButton::CycleEffectsScale,
+                    menu_button(
+                        format!("Screen effects: {}", percent_or_off(settings.effects_scale)),
+                        2
+                    ),
+                ),
+            ],
+        ))
+        .with_children(|parent| {
+            let mut index = 3;
+            for binding in Binding::ALL {
+                let value = if awaiting.0 == Some(binding) {
+                    if binding.is_gamepad() {
+                        "press a button...".to_string()
+                    } else {
+                        "press a key...".to_string()
+                    }
+                } else {
+                    bindings.describe(binding)
+                };
+
+                parent.spawn((
+                    SettingsButton::Rebind(binding),
+                    menu_button(format!("{}: {}", binding.name(), value), index),
+                ));
+                index += 1;
+            }
+
+            for conflict in bindings.conflicts() {
+                parent.spawn(label(conflict));
+            }
+
+            parent.spawn((SettingsButton::Back, menu_button("Back", index)));
+        })
+        .id()
+}
+
+#[butler_plugin]
+#[add_plugin(to_group = CorePlugins)]
+struct SettingsPlugin;
+
+#[add_system(schedule = Update, plugin = SettingsPlugin)]
+fn handle_settings_buttons(
+    mut pressed: EventReader<MenuButtonPressed>,
+    button_query: Query<&SettingsButton>,
+    mut settings: ResMut<Settings>,
+    mut awaiting: ResMut<AwaitingBinding>,
+    mut closed: EventWriter<SettingsClosed>,
+) {
+    // The press that finished a rebind shouldn't also activate a button
+    if awaiting.is_changed() {
+        pressed.clear();
+        return;
+    }
+
+    for event in pressed.read() {
+        let Ok(button) = button_query.get(event.0) else {
+            continue;
+        };
+
+        match button {
+            SettingsButton::ToggleAutoPause => {
+                settings.auto_pause_on_focus_loss = !settings.auto_pause_on_focus_loss;
+            }
+            SettingsButton::ToggleDamageVignette => {
+                settings.damage_vignette = !settings.damage_vignette;
+            }
+            SettingsButton::CycleEffectsScale => {
+                let current = EFFECTS_SCALES
+                    .iter()
+                    .position(|scale| *scale == settings.effects_scale)
+                    .unwrap_or(0);
+                settings.effects_scale = EFFECTS_SCALES[(current + 1) % EFFECTS_SCALES.len()];
+            }
+            SettingsButton::Rebind(binding) => {
+                awaiting.0 = Some(*binding);
+            }
+            SettingsButton::Back => {
+                awaiting.0 = None;
+                closed.write(SettingsClosed);
+            }
+        }
+    }
+}
+
+/// Assigns the next key or gamepad button pressed to the binding being rebound, Escape
+/// cancels.
+#[add_system(schedule = Update, plugin = SettingsPlugin, before = handle_settings_buttons)]
+fn capture_binding(
+    mut awaiting: ResMut<AwaitingBinding>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    // Ignore the press that started the rebind
+    let Some(binding) = awaiting.0.filter(|_| !awaiting.is_changed()) else {
+        return;
+    };
+
+    if keys.just_pressed(KeyCode::Escape) {
+        awaiting.0 = None;
+        return;
+    }
+
+    if binding.is_gamepad() {
+        let pressed = gamepads
+            .iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next().copied());
+        if let Some(button) = pressed {
+            bindings.dash_gamepad = button;
+            awaiting.0 = None;
+        }
+    } else if let Some(key) = keys.get_just_pressed().next() {
+        bindings.set_key(binding, *key);
+        awaiting.0 = None;
+    }
+}
+
+/// Rebuilds an open settings screen so labels reflect the new values.
+#[add_system(
+    schedule = Update,
+    plugin = SettingsPlugin,
+    run_if = resource_changed::<Settings>
+        .or(resource_changed::<KeyBindings>)
+        .or(resource_changed::<AwaitingBinding>),
+    after = handle_settings_buttons
+)]
+fn refresh_settings_screen(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    bindings: Res<KeyBindings>,
+    awaiting: Res<AwaitingBinding>,
+    screen_query: Query<Entity, With<SettingsScreen>>,
+) {
+    if screen_query.is_empty() {
+        return;
+    }
+
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    spawn_settings_screen(&mut commands, &settings, &bindings, &awaiting);
+}
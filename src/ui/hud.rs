@@ -0,0 +1,394 @@
+use bevy::ecs::entity_disabling::Disabled;
+
+use crate::actor::Health;
+use crate::player::*;
+use crate::progression::XpCurve;
+use crate::run::{RunStats, format_run_time};
+use crate::ui::*;
+use crate::weapon::components::*;
+use crate::weapon::definition::WeaponDefinitions;
+
+const PANEL_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.45);
+const BAR_BACKGROUND_COLOR: Color = Color::srgb(0.1, 0.1, 0.12);
+const HEALTH_COLOR: Color = Color::srgb(0.85, 0.15, 0.15);
+const EXPERIENCE_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+const GOLD_COLOR: Color = Color::srgb(1.0, 0.85, 0.1);
+const WEAPON_SLOT_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const BAR_WIDTH: f32 = 200.0;
+const WEAPON_SLOT_SIZE: f32 = 36.0;
+
+/// Root of the in-game HUD, drawn under every menu. Holds the run stats along the top
+/// and one [`PlayerHud`] per player along the bottom.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Hud;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PlayerRow;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct RunTimer;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct KillCounter;
+
+/// A single player's panel, ordered by slot along the bottom of the screen.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PlayerHud(Entity);
+
+/// The fill of a bar, its width is set as a percentage of the frame around it.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HealthBar(Entity);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HealthText(Entity);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct ExperienceBar(Entity);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct LevelText(Entity);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct GoldText(Entity);
+
+/// Row of equipped weapon icons, rebuilt when weapons are added or level up.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct WeaponSlots(Entity);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct DashIndicator(Entity);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct DownedIndicator(Entity);
+
+#[butler_plugin]
+#[add_plugin(to_group = RenderingPlugins)]
+struct HudPlugin;
+
+fn small_label(text: impl Into<String>, color: Color) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(color),
+    )
+}
+
+/// A fixed width frame with `fill` stretched across the given share of it.
+fn bar(fill: impl Bundle, color: Color) -> impl Bundle {
+    (
+        Node {
+            width: Val::Px(BAR_WIDTH),
+            height: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(BAR_BACKGROUND_COLOR),
+        children![(
+            fill,
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(color),
+        )],
+    )
+}
+
+#[add_system(schedule = OnEnter(GameState::InGame), plugin = HudPlugin)]
+fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        Hud,
+        Name::new("HUD"),
+        StateScoped(GameState::InGame),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::SpaceBetween,
+            padding: UiRect::all(Val::Px(16.0)),
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![
+            (
+                Node {
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(32.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                children![(RunTimer, label("")), (KillCounter, label(""))],
+            ),
+            (
+                PlayerRow,
+                Node {
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ),
+        ],
+    ));
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn spawn_player_huds(
+    mut commands: Commands,
+    player_query: Query<(Entity, &PlayerSlot), Added<Player>>,
+    row_query: Query<Entity, With<PlayerRow>>,
+) {
+    let Ok(row) = row_query.single() else {
+        return;
+    };
+
+    let mut players: Vec<_> = player_query.iter().collect();
+    players.sort_by_key(|(_, slot)| **slot);
+
+    for (player, slot) in players {
+        let name = (
+            Text::new(format!("P{}", slot.number())),
+            TextFont {
+                font_size: 24.0,
+                ..default()
+            },
+            TextColor(slot.color()),
+        );
+
+        commands.entity(row).with_child((
+            PlayerHud(player),
+            Name::new(format!("Player {} HUD", slot.number())),
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            Pickable::IGNORE,
+            children![
+                name,
+                (HealthText(player), small_label("", Color::WHITE)),
+                bar(HealthBar(player), HEALTH_COLOR),
+                (LevelText(player), small_label("", Color::WHITE)),
+                bar(ExperienceBar(player), EXPERIENCE_COLOR),
+                (GoldText(player), small_label("", GOLD_COLOR)),
+                (
+                    WeaponSlots(player),
+                    Node {
+                        column_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                ),
+                (DashIndicator(player), small_label("", Color::WHITE)),
+                (DownedIndicator(player), small_label("", HEALTH_COLOR)),
+            ],
+        ));
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame), run_if = resource_changed::<RunStats>)]
+fn update_run_stats(
+    stats: Res<RunStats>,
+    mut timer_query: Query<&mut Text, (With<RunTimer>, Without<KillCounter>)>,
+    mut kill_query: Query<&mut Text, (With<KillCounter>, Without<RunTimer>)>,
+) {
+    for mut text in timer_query.iter_mut() {
+        text.set_if_neq(Text::new(format_run_time(stats.elapsed_secs)));
+    }
+
+    for mut text in kill_query.iter_mut() {
+        text.set_if_neq(Text::new(format!("Kills {}", stats.kills)));
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn update_health(
+    player_query: Query<Ref<Health>, With<Player>>,
+    mut text_query: Query<(Ref<HealthText>, &mut Text)>,
+    mut bar_query: Query<(Ref<HealthBar>, &mut Node)>,
+) {
+    for (marker, mut text) in text_query.iter_mut() {
+        let Ok(health) = player_query.get(marker.0) else {
+            continue;
+        };
+        if health.is_changed() || marker.is_added() {
+            text.set_if_neq(Text::new(format!(
+                "HP {:.0}/{:.0}",
+                health.current.max(0.0),
+                health.max
+            )));
+        }
+    }
+
+    for (marker, mut node) in bar_query.iter_mut() {
+        let Ok(health) = player_query.get(marker.0) else {
+            continue;
+        };
+        if health.is_changed() || marker.is_added() {
+            let fraction = (health.current / health.max.max(1.0)).clamp(0.0, 1.0);
+            node.width = Val::Percent(fraction * 100.0);
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn update_experience(
+    curve: Res<XpCurve>,
+    player_query: Query<Ref<Experience>, With<Player>>,
+    mut text_query: Query<(Ref<LevelText>, &mut Text)>,
+    mut bar_query: Query<(Ref<ExperienceBar>, &mut Node)>,
+) {
+    for (marker, mut text) in text_query.iter_mut() {
+        let Ok(experience) = player_query.get(marker.0) else {
+            continue;
+        };
+        if experience.is_changed() || marker.is_added() {
+            let (level, ..) = curve.level_progress(experience.0);
+            text.set_if_neq(Text::new(format!("Level {}", level)));
+        }
+    }
+
+    for (marker, mut node) in bar_query.iter_mut() {
+        let Ok(experience) = player_query.get(marker.0) else {
+            continue;
+        };
+        if experience.is_changed() || marker.is_added() {
+            // Nothing left to fill at max level
+            let (_, into_level, needed) = curve.level_progress(experience.0);
+            let fraction = if needed > 0.0 {
+                (into_level / needed).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            node.width = Val::Percent(fraction * 100.0);
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn update_gold(
+    player_query: Query<Ref<Gold>, With<Player>>,
+    mut text_query: Query<(Ref<GoldText>, &mut Text)>,
+) {
+    for (marker, mut text) in text_query.iter_mut() {
+        let Ok(gold) = player_query.get(marker.0) else {
+            continue;
+        };
+        if gold.is_changed() || marker.is_added() {
+            text.set_if_neq(Text::new(format!("Gold {}", gold.0)));
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn update_weapon_slots(
+    mut commands: Commands,
+    definitions: WeaponDefinitions,
+    asset_server: Res<AssetServer>,
+    player_query: Query<Ref<EquippedWeapons>, With<Player>>,
+    // Downed players' weapons are disabled but still belong in their slots
+    weapon_query: Query<(&WeaponKind, Ref<WeaponLevel>), Or<(With<Disabled>, Without<Disabled>)>>,
+    slots_query: Query<(Entity, Ref<WeaponSlots>)>,
+) {
+    for (slots_entity, slots) in slots_query.iter() {
+        let Ok(equipped) = player_query.get(slots.0) else {
+            continue;
+        };
+
+        let weapons: Vec<_> = equipped
+            .0
+            .iter()
+            .filter_map(|entity| weapon_query.get(*entity).ok())
+            .collect();
+        let changed = equipped.is_changed()
+            || slots.is_added()
+            || weapons.iter().any(|(_, level)| level.is_changed());
+        if !changed {
+            continue;
+        }
+
+        commands
+            .entity(slots_entity)
+            .despawn_related::<Children>()
+            .with_children(|parent| {
+                for (kind, level) in weapons {
+                    let icon = definitions
+                        .get(*kind)
+                        .and_then(|definition| definition.image.clone())
+                        .map(|path| ImageNode::new(asset_server.load(path)))
+                        .unwrap_or_else(|| ImageNode::default().with_color(Color::NONE));
+
+                    parent.spawn((
+                        Name::new(format!("{} Slot", kind.name())),
+                        Node {
+                            width: Val::Px(WEAPON_SLOT_SIZE),
+                            height: Val::Px(WEAPON_SLOT_SIZE),
+                            justify_content: JustifyContent::FlexEnd,
+                            align_items: AlignItems::FlexEnd,
+                            ..default()
+                        },
+                        BackgroundColor(WEAPON_SLOT_COLOR),
+                        icon,
+                        children![small_label(level.0.to_string(), Color::WHITE)],
+                    ));
+                }
+            });
+    }
+}
+
+fn dash_text(dash: &Dash) -> String {
+    if dash.charges >= dash.max_charges {
+        format!("Dash {}/{}", dash.charges, dash.max_charges)
+    } else {
+        let remaining = dash.cooldown.remaining_secs();
+        format!(
+            "Dash {}/{}  ({:.1}s)",
+            dash.charges, dash.max_charges, remaining
+        )
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn update_dash_indicator(
+    player_query: Query<Ref<Dash>, With<Player>>,
+    mut indicator_query: Query<(Ref<DashIndicator>, &mut Text)>,
+) {
+    for (indicator, mut text) in indicator_query.iter_mut() {
+        let Ok(dash) = player_query.get(indicator.0) else {
+            continue;
+        };
+        if dash.is_changed() || indicator.is_added() {
+            text.set_if_neq(Text::new(dash_text(&dash)));
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = HudPlugin, run_if = in_state(GameState::InGame))]
+fn update_downed_indicator(
+    player_query: Query<Has<Downed>, With<Player>>,
+    mut indicator_query: Query<(&DownedIndicator, &mut Text)>,
+) {
+    for (indicator, mut text) in indicator_query.iter_mut() {
+        let downed = player_query.get(indicator.0).unwrap_or(false);
+        let new_text = if downed { "DOWN - touch to revive" } else { "" };
+        text.set_if_neq(Text::new(new_text));
+    }
+}
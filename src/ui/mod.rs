@@ -0,0 +1,183 @@
+use bevy::window::WindowResized;
+
+use crate::input::MenuAction;
+use crate::prelude::*;
+
+pub const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.2);
+pub const BUTTON_HOVERED_COLOR: Color = Color::srgb(0.25, 0.25, 0.35);
+pub const BUTTON_SELECTED_COLOR: Color = Color::srgb(0.35, 0.3, 0.55);
+pub const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+/// Window height the UI is laid out for, it's scaled up or down from there.
+const REFERENCE_HEIGHT: f32 = 720.0;
+
+/// A button that can also be highlighted by keyboard or gamepad navigation.
+/// Buttons are navigated in `index` order, only one menu should be on screen at a time.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+#[require(Button)]
+pub struct MenuButton {
+    pub index: usize,
+    pub selected: bool,
+}
+
+/// Emitted when a [`MenuButton`] is clicked or confirmed while selected.
+#[derive(Event, Debug, Clone)]
+#[add_event(plugin = UiPlugin)]
+pub struct MenuButtonPressed(pub Entity);
+
+/// Full screen, centred column drawn on top of the game.
+pub fn overlay() -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(16.0),
+            ..default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+        GlobalZIndex(10),
+    )
+}
+
+pub fn title(text: impl Into<String>) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: 48.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    )
+}
+
+pub fn label(text: impl Into<String>) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+    )
+}
+
+pub fn menu_button(text: impl Into<String>, index: usize) -> impl Bundle {
+    (
+        MenuButton {
+            index,
+            selected: index == 0,
+        },
+        Node {
+            width: Val::Px(480.0),
+            padding: UiRect::all(Val::Px(12.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BackgroundColor(BUTTON_COLOR),
+        children![(
+            Text::new(text),
+            TextFont {
+                font_size: 24.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )],
+    )
+}
+
+#[butler_plugin]
+#[add_plugin(to_group = RenderingPlugins)]
+struct UiPlugin;
+
+mod hud;
+
+#[add_system(schedule = Update, plugin = UiPlugin)]
+fn scale_ui_with_window(mut resized: EventReader<WindowResized>, mut ui_scale: ResMut<UiScale>) {
+    let Some(event) = resized.read().last() else {
+        return;
+    };
+
+    ui_scale.0 = (event.height / REFERENCE_HEIGHT).max(0.5);
+}
+
+#[add_system(schedule = Update, plugin = UiPlugin)]
+fn update_menu_button_colors(
+    mut query: Query<
+        (&Interaction, &MenuButton, &mut BackgroundColor),
+        Or<(Changed<Interaction>, Changed<MenuButton>)>,
+    >,
+) {
+    for (interaction, button, mut background) in query.iter_mut() {
+        background.0 = match (interaction, button.selected) {
+            (Interaction::Pressed, _) | (_, true) => BUTTON_SELECTED_COLOR,
+            (Interaction::Hovered, false) => BUTTON_HOVERED_COLOR,
+            (Interaction::None, false) => BUTTON_COLOR,
+        };
+    }
+}
+
+#[add_system(schedule = Update, plugin = UiPlugin)]
+fn navigate_menu_buttons(
+    menu_actions: Res<ActionState<MenuAction>>,
+    mut query: Query<&mut MenuButton>,
+) {
+    let step: isize = if menu_actions.just_pressed(&MenuAction::Up) {
+        -1
+    } else if menu_actions.just_pressed(&MenuAction::Down) {
+        1
+    } else {
+        return;
+    };
+
+    let count = query.iter().count();
+    if count == 0 {
+        return;
+    }
+
+    let mut order: Vec<usize> = query.iter().map(|button| button.index).collect();
+    order.sort_unstable();
+
+    let current = query
+        .iter()
+        .find(|button| button.selected)
+        .and_then(|button| order.iter().position(|index| *index == button.index))
+        .unwrap_or(0);
+    let next = order[(current as isize + step).rem_euclid(count as isize) as usize];
+
+    for mut button in query.iter_mut() {
+        let selected = button.index == next;
+        if button.selected != selected {
+            button.selected = selected;
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = UiPlugin, after = navigate_menu_buttons)]
+fn press_menu_buttons(
+    menu_actions: Res<ActionState<MenuAction>>,
+    interaction_query: Query<(Entity, &Interaction), (With<MenuButton>, Changed<Interaction>)>,
+    button_query: Query<(Entity, &MenuButton)>,
+    mut pressed: EventWriter<MenuButtonPressed>,
+) {
+    for (entity, interaction) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            pressed.write(MenuButtonPressed(entity));
+            return;
+        }
+    }
+
+    if !menu_actions.just_pressed(&MenuAction::Confirm) {
+        return;
+    }
+
+    if let Some((entity, _)) = button_query.iter().find(|(_, button)| button.selected) {
+        pressed.write(MenuButtonPressed(entity));
+    }
+}
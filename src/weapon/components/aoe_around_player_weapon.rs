@@ -0,0 +1,55 @@
+use crate::prelude::*;
+use crate::weapon::components::*;
+use crate::weapon::definition::{AoeAroundPlayerStats, WeaponComponent};
+
+#[derive(Component, Debug, Reflect)]
+#[require(Weapon, HitCooldowns)]
+pub struct AoeAroundPlayerWeapon {
+    pub damage: f32,
+    pub radius: f32,
+    pub attack_rate: f32,           // Pulses per second, unused by auras
+    pub duration: Option<f32>,      // How long a pulse's ring takes to expand and fade
+    pub damage_over_time: bool,     // Persistent aura when true, periodic pulse otherwise
+    pub tick_interval: Option<f32>, // Seconds between aura ticks on the same enemy
+    pub attack_timer: Timer,
+}
+
+impl WeaponComponent for AoeAroundPlayerWeapon {
+    type Stats = AoeAroundPlayerStats;
+
+    fn apply_stats(&mut self, stats: &AoeAroundPlayerStats) {
+        self.damage = stats.damage;
+        self.radius = stats.radius;
+        self.attack_rate = stats.attack_rate;
+        self.duration = stats.duration;
+        self.damage_over_time = stats.damage_over_time;
+        self.tick_interval = stats.tick_interval;
+    }
+}
+
+impl AoeAroundPlayerWeapon {
+    pub fn tick_interval(&self) -> f32 {
+        self.tick_interval.unwrap_or(0.5)
+    }
+
+    pub fn pulse_duration(&self) -> f32 {
+        self.duration.unwrap_or(0.3)
+    }
+}
+
+/// Unit annulus shared by every aura and pulse ring, scaled to the weapon's radius.
+#[derive(Resource, Debug)]
+pub struct AoeRingMesh(pub Handle<Mesh>);
+
+/// Persistent ring showing an aura's radius, spawned as a child of the weapon.
+#[derive(Component, Debug)]
+pub struct AoeAuraRing {
+    pub weapon: Entity,
+}
+
+/// Ring spawned on every pulse, expands out to `radius` while fading.
+#[derive(Component, Debug)]
+pub struct AoePulseRing {
+    pub radius: f32,
+    pub timer: Timer,
+}
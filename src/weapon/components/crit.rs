@@ -0,0 +1,34 @@
+use bevy::ecs::system::SystemParam;
+use rand::Rng;
+
+use crate::actor::DamageEvent;
+use crate::prelude::*;
+use crate::weapon::components::WeaponRng;
+
+/// Chance for every hit the weapon lands to be a crit, `0.0..=1.0`.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct CritChance(pub f32);
+
+/// Writes the [`DamageEvent`]s of weapon hits, rolling for crits from the weapon's
+/// [`CritChance`] with the seeded [`WeaponRng`].
+#[derive(SystemParam)]
+pub struct WeaponHits<'w, 's> {
+    events: EventWriter<'w, DamageEvent>,
+    rng: ResMut<'w, WeaponRng>,
+    crit_query: Query<'w, 's, &'static CritChance>,
+}
+
+impl WeaponHits<'_, '_> {
+    pub fn write(&mut self, weapon: Entity, target: Entity, amount: f32) {
+        let chance = self
+            .crit_query
+            .get(weapon)
+            .map(|crit| crit.0.clamp(0.0, 1.0))
+            .unwrap_or(0.0);
+        let crit = chance > 0.0 && self.rng.0.gen_bool(chance as f64);
+
+        self.events
+            .write(DamageEvent::new(weapon, target, amount).with_crit(crit));
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::weapon::components::*;
+use crate::weapon::definition::{DirectionalMeleeStats, WeaponComponent};
+
+#[derive(Debug, Reflect, Clone, Copy, PartialEq, Deserialize)]
+pub enum DirectionMode {
+    Moving,
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Always the same direction, written as `Custom((x, y, z))`, `z` is ignored.
+    Custom(Vec3),
+}
+
+impl DirectionMode {
+    /// Direction to attack in, `facing` is the owner's last movement direction.
+    pub fn resolve(&self, facing: Vec2) -> Vec2 {
+        match self {
+            DirectionMode::Moving => facing,
+            DirectionMode::Left => Vec2::NEG_X,
+            DirectionMode::Right => Vec2::X,
+            DirectionMode::Up => Vec2::Y,
+            DirectionMode::Down => Vec2::NEG_Y,
+            DirectionMode::Custom(direction) => direction.truncate(),
+        }
+        .normalize_or(Vec2::X)
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[require(Weapon)]
+pub struct DirectionalMeleeWeapon {
+    pub damage: f32,
+    pub range: f32,            // How far the attack reaches in the given direction
+    pub attack_angle_deg: f32, // Cone angle or arc of attack (e.g. 60° cone)
+    pub attack_rate: f32,      // Attacks per second
+    pub direction_mode: DirectionMode,
+    pub slash_image: Handle<Image>,
+    pub attack_timer: Timer,
+}
+
+impl WeaponComponent for DirectionalMeleeWeapon {
+    type Stats = DirectionalMeleeStats;
+
+    fn apply_stats(&mut self, stats: &DirectionalMeleeStats) {
+        self.damage = stats.damage;
+        self.range = stats.range;
+        self.attack_angle_deg = stats.attack_angle_deg;
+        self.attack_rate = stats.attack_rate;
+        self.direction_mode = stats.direction_mode;
+    }
+}
+
+/// Visual sweep spawned as a child of the weapon on every attack.
+#[derive(Component, Debug)]
+#[require(Sprite)]
+pub struct Slash {
+    pub direction: Vec2,
+    pub half_angle: f32,
+    pub radius: f32,
+    pub timer: Timer,
+}
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use bevy::platform::collections::HashMap;
+
+use crate::prelude::*;
+
+/// Per-target cooldowns for anything that deals damage on contact. Put it on the weapon
+/// to share cooldowns between all of its satellites, or on a satellite to track them
+/// separately. Expired and despawned targets are dropped every frame.
+#[derive(Component, Debug, Default)]
+pub struct HitCooldowns(HashMap<Entity, Timer>);
+
+impl HitCooldowns {
+    pub fn can_hit(&self, target: Entity) -> bool {
+        !self.0.contains_key(&target)
+    }
+
+    /// Records a hit on `target`, it can't be hit again for `cooldown` seconds.
+    pub fn register(&mut self, target: Entity, cooldown: f32) {
+        self.0
+            .insert(target, Timer::from_seconds(cooldown, TimerMode::Once));
+    }
+
+    /// Registers a hit if `target` is off cooldown, returns whether it was.
+    pub fn try_hit(&mut self, target: Entity, cooldown: f32) -> bool {
+        if !self.can_hit(target) {
+            return false;
+        }
+
+        self.register(target, cooldown);
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn tick(&mut self, delta: Duration, is_alive: impl Fn(Entity) -> bool) {
+        self.0.retain(|target, timer| {
+            timer.tick(delta);
+            !timer.finished() && is_alive(*target)
+        });
+    }
+}
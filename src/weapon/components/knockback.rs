@@ -0,0 +1,6 @@
+use crate::prelude::*;
+
+/// Speed enemies are pushed away from the weapon's holder with when hit.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct KnockbackStrength(pub f32);
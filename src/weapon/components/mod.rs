@@ -1,6 +1,21 @@
 mod weapon;
 pub(crate) use weapon::*;
 
+mod hit_cooldowns;
+pub(crate) use hit_cooldowns::*;
+
+mod targeting;
+pub(crate) use targeting::*;
+
+mod on_hit_effect;
+pub(crate) use on_hit_effect::*;
+
+mod knockback;
+pub(crate) use knockback::*;
+
+mod crit;
+pub(crate) use crit::*;
+
 mod orbit_common;
 pub(crate) use orbit_common::*;
 
@@ -10,6 +25,24 @@ pub(crate) use orbit_weapon::*;
 mod orbit_and_lunge_weapon;
 pub(crate) use orbit_and_lunge_weapon::*;
 
+mod directional_melee_weapon;
+pub(crate) use directional_melee_weapon::*;
+
+mod projectile;
+pub(crate) use projectile::*;
+
+mod targetted_projectile_weapon;
+pub(crate) use targetted_projectile_weapon::*;
+
+mod random_projectile_weapon;
+pub(crate) use random_projectile_weapon::*;
+
+mod aoe_around_player_weapon;
+pub(crate) use aoe_around_player_weapon::*;
+
+mod weapon_rng;
+pub(crate) use weapon_rng::*;
+
 // use bevy::platform::collections::HashMap;
 
 // #[derive(Debug, Reflect)]
@@ -41,54 +74,3 @@ pub(crate) use orbit_and_lunge_weapon::*;
 //     pub lunge_range: f32,
 //     pub lunge_cooldown: f32,
 // }
-
-// #[derive(Debug, Reflect)]
-// pub struct DirectionalMeleeWeaponData {
-//     pub damage: f32,
-//     pub range: f32,            // How far the attack reaches in the given direction
-//     pub attack_angle_deg: f32, // Cone angle or arc of attack (e.g. 60° cone)
-//     pub attack_rate: f32,      // Attacks per second
-//     pub direction_mode: DirectionMode,
-// }
-
-// #[derive(Debug, Reflect)]
-// pub enum DirectionMode {
-//     Moving,
-//     Left,
-//     Right,
-//     Up,
-//     Down,
-//     Custom(Vec3),
-// }
-
-// #[derive(Debug, Reflect)]
-// pub struct TargettedProjectileWeaponData {
-//     pub damage: f32,
-//     pub projectile_speed: f32,
-//     pub projectile_lifetime: f32,
-//     pub attack_rate: f32,
-//     pub max_targets: usize,
-//     pub homing: bool,
-//     pub spread_angle_deg: f32,
-// }
-
-// #[derive(Debug, Reflect)]
-// pub struct RandomProjectileWeaponData {
-//     pub damage: f32,
-//     pub projectile_speed: f32,
-//     pub projectile_lifetime: f32,
-//     pub attack_rate: f32,
-//     pub max_projectiles_per_shot: usize,
-//     pub spread_angle_deg: f32,
-//     pub firing_arc_deg: f32,
-// }
-
-// #[derive(Debug, Reflect)]
-// pub struct AoeAroundPlayerWeaponData {
-//     pub damage: f32,
-//     pub radius: f32,
-//     pub attack_rate: f32,
-//     pub duration: Option<f32>,
-//     pub damage_over_time: bool,
-//     pub tick_interval: Option<f32>,
-// }
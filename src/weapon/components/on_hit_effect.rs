@@ -0,0 +1,7 @@
+use crate::actor::StatusEffectKind;
+use crate::prelude::*;
+
+/// Status effect put on every enemy the weapon damages.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct OnHitEffect(pub StatusEffectKind);
@@ -1,10 +1,10 @@
-use bevy::platform::collections::HashSet;
-
 use crate::prelude::*;
 use crate::weapon::components::*;
+use crate::weapon::definition::{OrbitAndLungeStats, WeaponComponent};
 
+/// Each satellite tracks its own [`HitCooldowns`] so it hits every enemy once per lunge.
 #[derive(Component)]
-#[require(Sprite)]
+#[require(Sprite, HitCooldowns)]
 pub struct OrbitAndLungeSatellite {
     pub index: usize,
     pub weapon: Entity,
@@ -12,7 +12,11 @@ pub struct OrbitAndLungeSatellite {
     pub progress: f32,
     pub cooldown_timer: Timer,
     pub lunge_target: Option<Vec3>,
-    pub hit_entities_this_lunge: HashSet<Entity>,
+    /// Enemy picked for the current lunge, other satellites of the weapon leave it alone.
+    pub target: Option<Entity>,
+    /// World position the satellite lunged out from this frame, damage is swept from here
+    /// to where it ended up.
+    pub sweep_from: Option<Vec3>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -23,7 +27,7 @@ pub enum LungeState {
 }
 
 #[derive(Component, Debug, Reflect)]
-#[require(Weapon)]
+#[require(Weapon, TargetingStrategy)]
 pub struct OrbitAndLungeWeapon {
     pub orbit_speed: f32,
     pub orbit_radius: f32,
@@ -34,3 +38,17 @@ pub struct OrbitAndLungeWeapon {
     pub lunge_range: f32,
     pub lunge_cooldown: f32,
 }
+
+impl WeaponComponent for OrbitAndLungeWeapon {
+    type Stats = OrbitAndLungeStats;
+
+    fn apply_stats(&mut self, stats: &OrbitAndLungeStats) {
+        self.orbit_speed = stats.orbit_speed;
+        self.orbit_radius = stats.orbit_radius;
+        self.orbit_direction = stats.orbit_direction;
+        self.satellite_count = stats.satellite_count;
+        self.lunge_damage = stats.lunge_damage;
+        self.lunge_range = stats.lunge_range;
+        self.lunge_cooldown = stats.lunge_cooldown;
+    }
+}
@@ -1,6 +1,8 @@
+use serde::Deserialize;
+
 use crate::prelude::*;
 
-#[derive(Debug, Reflect)]
+#[derive(Debug, Reflect, Clone, Copy, PartialEq, Deserialize)]
 pub enum OrbitDirection {
     Clockwise,
     CounterClockwise,
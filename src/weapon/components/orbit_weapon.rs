@@ -1,7 +1,6 @@
-use bevy::platform::collections::HashMap;
-
 use crate::prelude::*;
 use crate::weapon::components::*;
+use crate::weapon::definition::{OrbitStats, WeaponComponent};
 
 #[derive(Component)]
 #[require(Sprite)]
@@ -11,7 +10,7 @@ pub struct OrbitWeaponSatellite {
 }
 
 #[derive(Component, Debug, Reflect)]
-#[require(Weapon)]
+#[require(Weapon, HitCooldowns)]
 pub struct OrbitWeapon {
     pub orbit_speed: f32,
     pub orbit_radius: f32,
@@ -20,5 +19,17 @@ pub struct OrbitWeapon {
     pub satellite_image: Handle<Image>,
     pub contact_damage: f32,
     pub contact_cooldown_per_entity: f32,
-    pub contact_cooldown_map: HashMap<Entity, Timer>,
+}
+
+impl WeaponComponent for OrbitWeapon {
+    type Stats = OrbitStats;
+
+    fn apply_stats(&mut self, stats: &OrbitStats) {
+        self.orbit_speed = stats.orbit_speed;
+        self.orbit_radius = stats.orbit_radius;
+        self.orbit_direction = stats.orbit_direction;
+        self.satellite_count = stats.satellite_count;
+        self.contact_damage = stats.contact_damage;
+        self.contact_cooldown_per_entity = stats.contact_cooldown_per_entity;
+    }
 }
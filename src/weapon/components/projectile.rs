@@ -0,0 +1,36 @@
+use crate::prelude::*;
+
+/// Free flying projectile, spawned at the root of the world rather than under its weapon.
+#[derive(Component, Debug)]
+#[require(Sprite)]
+pub struct Projectile {
+    pub weapon: Entity,
+    pub damage: f32,
+    pub speed: f32,
+    pub direction: Vec2,
+    pub radius: f32,
+    pub lifetime: Timer,
+    pub homing: Option<Homing>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Homing {
+    pub target: Option<Entity>,
+    /// Radians per second the projectile can turn towards its target.
+    pub turn_rate: f32,
+}
+
+impl Projectile {
+    pub fn bundle(self, image: Handle<Image>, position: Vec3) -> impl Bundle {
+        let rotation =
+            Quat::from_rotation_z(self.direction.to_angle() - std::f32::consts::FRAC_PI_2);
+
+        (
+            Name::new("Projectile"),
+            StateScoped(GameState::InGame),
+            self,
+            Sprite { image, ..default() },
+            Transform::from_translation(position.with_z(1.0)).with_rotation(rotation),
+        )
+    }
+}
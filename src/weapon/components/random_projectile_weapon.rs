@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use crate::weapon::components::*;
+use crate::weapon::definition::{RandomProjectileStats, WeaponComponent};
+
+#[derive(Component, Debug, Reflect)]
+#[require(Weapon)]
+pub struct RandomProjectileWeapon {
+    pub damage: f32,
+    pub projectile_speed: f32,
+    pub projectile_lifetime: f32,
+    pub attack_rate: f32, // Volleys per second
+    pub max_projectiles_per_shot: usize,
+    pub spread_angle_deg: f32, // Random jitter applied to each projectile
+    pub firing_arc_deg: f32,   // Arc around the owner's facing that projectiles are fired into
+    pub projectile_image: Handle<Image>,
+    pub attack_timer: Timer,
+}
+
+impl WeaponComponent for RandomProjectileWeapon {
+    type Stats = RandomProjectileStats;
+
+    fn apply_stats(&mut self, stats: &RandomProjectileStats) {
+        self.damage = stats.damage;
+        self.projectile_speed = stats.projectile_speed;
+        self.projectile_lifetime = stats.projectile_lifetime;
+        self.attack_rate = stats.attack_rate;
+        self.max_projectiles_per_shot = stats.max_projectiles_per_shot;
+        self.spread_angle_deg = stats.spread_angle_deg;
+        self.firing_arc_deg = stats.firing_arc_deg;
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+/// How a weapon picks which enemies to go after.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[reflect(Component)]
+pub enum TargetingStrategy {
+    #[default]
+    Nearest,
+    Farthest,
+    LowestHealth,
+    HighestHealth,
+    Random,
+    /// The enemy with the most other enemies packed around it.
+    DensestCluster,
+    /// Bosses, then elites, then everything else, nearest first within each rank.
+    EliteFirst,
+}
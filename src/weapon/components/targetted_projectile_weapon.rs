@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use crate::weapon::components::*;
+use crate::weapon::definition::{TargettedProjectileStats, WeaponComponent};
+
+#[derive(Component, Debug, Reflect)]
+#[require(Weapon, TargetingStrategy)]
+pub struct TargettedProjectileWeapon {
+    pub damage: f32,
+    pub projectile_speed: f32,
+    pub projectile_lifetime: f32,
+    pub attack_rate: f32, // Volleys per second
+    pub max_targets: usize,
+    pub homing: bool,
+    pub spread_angle_deg: f32, // Angle between projectiles sharing a target
+    pub projectile_image: Handle<Image>,
+    pub attack_timer: Timer,
+}
+
+impl WeaponComponent for TargettedProjectileWeapon {
+    type Stats = TargettedProjectileStats;
+
+    fn apply_stats(&mut self, stats: &TargettedProjectileStats) {
+        self.damage = stats.damage;
+        self.projectile_speed = stats.projectile_speed;
+        self.projectile_lifetime = stats.projectile_lifetime;
+        self.attack_rate = stats.attack_rate;
+        self.max_targets = stats.max_targets;
+        self.homing = stats.homing;
+        self.spread_angle_deg = stats.spread_angle_deg;
+    }
+}
@@ -1,9 +1,60 @@
+use serde::Deserialize;
+
 use crate::prelude::*;
 
+pub const MAX_WEAPON_LEVEL: u32 = 8;
+pub const MAX_EQUIPPED_WEAPONS: usize = 6;
+
+/// Weapons the owner is carrying, each is also a child of the owner. Taking a weapon out
+/// of the list unequips it, the weapon and everything it spawned as children are despawned.
 #[derive(Component, Reflect, Debug, Default)]
 #[reflect(Component)]
 pub struct EquippedWeapons(pub Vec<Entity>);
 
 #[derive(Component, Reflect, Debug, Default)]
 #[reflect(Component)]
+#[require(WeaponLevel)]
 pub struct Weapon;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct WeaponLevel(pub u32);
+
+impl Default for WeaponLevel {
+    fn default() -> Self {
+        WeaponLevel(1)
+    }
+}
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[reflect(Component)]
+pub enum WeaponKind {
+    Orbit,
+    OrbitAndLunge,
+    DirectionalMelee,
+    TargettedProjectile,
+    RandomProjectile,
+    AoeAroundPlayer,
+}
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 6] = [
+        WeaponKind::Orbit,
+        WeaponKind::OrbitAndLunge,
+        WeaponKind::DirectionalMelee,
+        WeaponKind::TargettedProjectile,
+        WeaponKind::RandomProjectile,
+        WeaponKind::AoeAroundPlayer,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeaponKind::Orbit => "Orbiting Daggers",
+            WeaponKind::OrbitAndLunge => "Lunging Daggers",
+            WeaponKind::DirectionalMelee => "Sweeping Blade",
+            WeaponKind::TargettedProjectile => "Seeking Knives",
+            WeaponKind::RandomProjectile => "Scattershot",
+            WeaponKind::AoeAroundPlayer => "Withering Aura",
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::prelude::*;
+use crate::weapon::WeaponPlugin;
+
+/// Shared source of randomness for weapons, reseeded from the world seed at the start of
+/// every run so the same seed always plays out the same way.
+#[derive(Resource, Debug)]
+#[insert_resource(plugin = WeaponPlugin)]
+pub struct WeaponRng(pub StdRng);
+
+impl Default for WeaponRng {
+    fn default() -> Self {
+        Self(StdRng::seed_from_u64(0))
+    }
+}
+
+impl WeaponRng {
+    pub fn reseed(&mut self, seed: u64) {
+        self.0 = StdRng::seed_from_u64(seed);
+    }
+}
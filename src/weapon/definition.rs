@@ -0,0 +1,423 @@
+use bevy::ecs::component::Mutable;
+use bevy::ecs::system::SystemParam;
+use serde::Deserialize;
+
+use crate::actor::StatusEffectKind;
+use crate::items::StatModifiers;
+use crate::prelude::*;
+use crate::weapon::components::*;
+
+/// Every weapon's base stats and level table, loaded from `data/weapons.ron`.
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct WeaponCatalog {
+    pub weapons: Vec<WeaponDefinition>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct WeaponAssets {
+    #[asset(path = "data/weapons.ron")]
+    pub catalog: Handle<WeaponCatalog>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeaponDefinition {
+    pub kind: WeaponKind,
+    pub image: Option<String>,
+    pub stats: WeaponStats,
+    /// Only used by weapons that pick targets, defaults to nearest first.
+    #[serde(default)]
+    pub targeting: Option<TargetingStrategy>,
+    /// Status effect applied to everything the weapon hits.
+    #[serde(default)]
+    pub on_hit: Option<StatusEffectKind>,
+    /// Speed enemies are pushed back with when hit, none when left out.
+    #[serde(default)]
+    pub knockback: f32,
+    /// Chance for each hit to crit, never when left out.
+    #[serde(default)]
+    pub crit_chance: f32,
+    #[serde(default)]
+    pub levels: Vec<LevelDelta>,
+}
+
+/// Changes applied once the weapon reaches `level`, on top of every earlier level.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LevelDelta {
+    pub level: u32,
+    pub changes: Vec<StatChange>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum StatChange {
+    Add(WeaponStat, f32),
+    Multiply(WeaponStat, f32),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponStat {
+    Damage,
+    Radius,
+    Range,
+    OrbitSpeed,
+    AttackRate,
+    AttackAngle,
+    /// Satellites, targets or projectiles per shot depending on the weapon.
+    Count,
+    ProjectileSpeed,
+    ProjectileLifetime,
+    SpreadAngle,
+    FiringArc,
+    Cooldown,
+    TickInterval,
+    Duration,
+}
+
+impl StatChange {
+    fn stat(&self) -> WeaponStat {
+        match self {
+            StatChange::Add(stat, _) | StatChange::Multiply(stat, _) => *stat,
+        }
+    }
+
+    fn apply(&self, value: f32) -> f32 {
+        match self {
+            StatChange::Add(_, amount) => value + amount,
+            StatChange::Multiply(_, factor) => value * factor,
+        }
+    }
+
+    fn apply_count(&self, value: &mut usize) {
+        *value = self.apply(*value as f32).round().max(0.0) as usize;
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct OrbitStats {
+    pub orbit_speed: f32,
+    pub orbit_radius: f32,
+    pub orbit_direction: OrbitDirection,
+    pub satellite_count: usize,
+    pub contact_damage: f32,
+    pub contact_cooldown_per_entity: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct OrbitAndLungeStats {
+    pub orbit_speed: f32,
+    pub orbit_radius: f32,
+    pub orbit_direction: OrbitDirection,
+    pub satellite_count: usize,
+    pub lunge_damage: f32,
+    pub lunge_range: f32,
+    pub lunge_cooldown: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DirectionalMeleeStats {
+    pub damage: f32,
+    pub range: f32,
+    pub attack_angle_deg: f32,
+    pub attack_rate: f32,
+    pub direction_mode: DirectionMode,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TargettedProjectileStats {
+    pub damage: f32,
+    pub projectile_speed: f32,
+    pub projectile_lifetime: f32,
+    pub attack_rate: f32,
+    pub max_targets: usize,
+    pub homing: bool,
+    pub spread_angle_deg: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RandomProjectileStats {
+    pub damage: f32,
+    pub projectile_speed: f32,
+    pub projectile_lifetime: f32,
+    pub attack_rate: f32,
+    pub max_projectiles_per_shot: usize,
+    pub spread_angle_deg: f32,
+    pub firing_arc_deg: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AoeAroundPlayerStats {
+    pub damage: f32,
+    pub radius: f32,
+    pub attack_rate: f32,
+    pub duration: Option<f32>,
+    pub damage_over_time: bool,
+    pub tick_interval: Option<f32>,
+}
+
+/// Base stat block of a weapon, the variant must match the definition's `kind`.
+#[derive(Deserialize, Debug, Clone)]
+pub enum WeaponStats {
+    Orbit(OrbitStats),
+    OrbitAndLunge(OrbitAndLungeStats),
+    DirectionalMelee(DirectionalMeleeStats),
+    TargettedProjectile(TargettedProjectileStats),
+    RandomProjectile(RandomProjectileStats),
+    AoeAroundPlayer(AoeAroundPlayerStats),
+}
+
+impl WeaponStats {
+    pub fn kind(&self) -> WeaponKind {
+        match self {
+            WeaponStats::Orbit(_) => WeaponKind::Orbit,
+            WeaponStats::OrbitAndLunge(_) => WeaponKind::OrbitAndLunge,
+            WeaponStats::DirectionalMelee(_) => WeaponKind::DirectionalMelee,
+            WeaponStats::TargettedProjectile(_) => WeaponKind::TargettedProjectile,
+            WeaponStats::RandomProjectile(_) => WeaponKind::RandomProjectile,
+            WeaponStats::AoeAroundPlayer(_) => WeaponKind::AoeAroundPlayer,
+        }
+    }
+
+    /// Applies a single change, returns false when this weapon has no such stat.
+    fn apply(&mut self, change: &StatChange) -> bool {
+        let stat = change.stat();
+        match self {
+            WeaponStats::Orbit(stats) => match stat {
+                WeaponStat::Damage => stats.contact_damage = change.apply(stats.contact_damage),
+                WeaponStat::Radius => stats.orbit_radius = change.apply(stats.orbit_radius),
+                WeaponStat::OrbitSpeed => stats.orbit_speed = change.apply(stats.orbit_speed),
+                WeaponStat::Count => change.apply_count(&mut stats.satellite_count),
+                WeaponStat::Cooldown => {
+                    stats.contact_cooldown_per_entity =
+                        change.apply(stats.contact_cooldown_per_entity)
+                }
+                _ => return false,
+            },
+            WeaponStats::OrbitAndLunge(stats) => match stat {
+                WeaponStat::Damage => stats.lunge_damage = change.apply(stats.lunge_damage),
+                WeaponStat::Radius => stats.orbit_radius = change.apply(stats.orbit_radius),
+                WeaponStat::Range => stats.lunge_range = change.apply(stats.lunge_range),
+                WeaponStat::OrbitSpeed => stats.orbit_speed = change.apply(stats.orbit_speed),
+                WeaponStat::Count => change.apply_count(&mut stats.satellite_count),
+                WeaponStat::Cooldown => stats.lunge_cooldown = change.apply(stats.lunge_cooldown),
+                _ => return false,
+            },
+            WeaponStats::DirectionalMelee(stats) => match stat {
+                WeaponStat::Damage => stats.damage = change.apply(stats.damage),
+                WeaponStat::Range => stats.range = change.apply(stats.range),
+                WeaponStat::AttackRate => stats.attack_rate = change.apply(stats.attack_rate),
+                WeaponStat::AttackAngle => {
+                    stats.attack_angle_deg = change.apply(stats.attack_angle_deg).min(360.0)
+                }
+                _ => return false,
+            },
+            WeaponStats::TargettedProjectile(stats) => match stat {
+                WeaponStat::Damage => stats.damage = change.apply(stats.damage),
+                WeaponStat::AttackRate => stats.attack_rate = change.apply(stats.attack_rate),
+                WeaponStat::Count => change.apply_count(&mut stats.max_targets),
+                WeaponStat::ProjectileSpeed => {
+                    stats.projectile_speed = change.apply(stats.projectile_speed)
+                }
+                WeaponStat::ProjectileLifetime => {
+                    stats.projectile_lifetime = change.apply(stats.projectile_lifetime)
+                }
+                WeaponStat::SpreadAngle => {
+                    stats.spread_angle_deg = change.apply(stats.spread_angle_deg)
+                }
+                _ => return false,
+            },
+            WeaponStats::RandomProjectile(stats) => match stat {
+                WeaponStat::Damage => stats.damage = change.apply(stats.damage),
+                WeaponStat::AttackRate => stats.attack_rate = change.apply(stats.attack_rate),
+                WeaponStat::Count => change.apply_count(&mut stats.max_projectiles_per_shot),
+                WeaponStat::ProjectileSpeed => {
+                    stats.projectile_speed = change.apply(stats.projectile_speed)
+                }
+                WeaponStat::ProjectileLifetime => {
+                    stats.projectile_lifetime = change.apply(stats.projectile_lifetime)
+                }
+                WeaponStat::SpreadAngle => {
+                    stats.spread_angle_deg = change.apply(stats.spread_angle_deg)
+                }
+                WeaponStat::FiringArc => {
+                    stats.firing_arc_deg = change.apply(stats.firing_arc_deg).min(360.0)
+                }
+                _ => return false,
+            },
+            WeaponStats::AoeAroundPlayer(stats) => match stat {
+                WeaponStat::Damage => stats.damage = change.apply(stats.damage),
+                WeaponStat::Radius => stats.radius = change.apply(stats.radius),
+                WeaponStat::AttackRate => stats.attack_rate = change.apply(stats.attack_rate),
+                WeaponStat::TickInterval => {
+                    stats.tick_interval = stats.tick_interval.map(|value| change.apply(value))
+                }
+                WeaponStat::Duration => {
+                    stats.duration = stats.duration.map(|value| change.apply(value))
+                }
+                _ => return false,
+            },
+        }
+
+        true
+    }
+}
+
+/// The stat block of a single [`WeaponKind`], held by one of the [`WeaponStats`] variants.
+pub trait KindStats: Sized {
+    const KIND: WeaponKind;
+
+    /// Pulls these stats out, `None` when `stats` belong to another kind of weapon.
+    fn from_weapon_stats(stats: WeaponStats) -> Option<Self>;
+}
+
+macro_rules! kind_stats {
+    ($($kind:ident($stats:ty)),* $(,)?) => {
+        $(
+            impl KindStats for $stats {
+                const KIND: WeaponKind = WeaponKind::$kind;
+
+                fn from_weapon_stats(stats: WeaponStats) -> Option<Self> {
+                    match stats {
+                        WeaponStats::$kind(stats) => Some(stats),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+kind_stats!(
+    Orbit(OrbitStats),
+    OrbitAndLunge(OrbitAndLungeStats),
+    DirectionalMelee(DirectionalMeleeStats),
+    TargettedProjectile(TargettedProjectileStats),
+    RandomProjectile(RandomProjectileStats),
+    AoeAroundPlayer(AoeAroundPlayerStats),
+);
+
+/// A concrete weapon component, configured by the stats of its [`WeaponKind`].
+pub trait WeaponComponent: Component<Mutability = Mutable> {
+    type Stats: KindStats;
+
+    /// Takes on the stats of a new level. Only stats are overwritten, timers and anything
+    /// else tracked while attacking keep running.
+    fn apply_stats(&mut self, stats: &Self::Stats);
+}
+
+fn add_count(count: usize, amount: f32) -> usize {
+    (count as f32 + amount).round().max(0.0) as usize
+}
+
+impl WeaponStats {
+    /// Final stats with the owner's passive modifiers applied. Always start from the base
+    /// stats of the level rather than the weapon's current ones, so removing a modifier
+    /// gets back exactly to the base.
+    pub fn with_modifiers(&self, modifiers: &StatModifiers) -> WeaponStats {
+        let might = 1.0 + modifiers.might;
+        let area = 1.0 + modifiers.area;
+        let cooldown = modifiers.cooldown_multiplier();
+        let amount = modifiers.amount;
+        let projectile_speed = 1.0 + modifiers.projectile_speed;
+        let duration = 1.0 + modifiers.duration;
+
+        let mut stats = self.clone();
+        match &mut stats {
+            WeaponStats::Orbit(stats) => {
+                stats.contact_damage *= might;
+                stats.orbit_radius *= area;
+                stats.satellite_count = add_count(stats.satellite_count, amount);
+                stats.contact_cooldown_per_entity *= cooldown;
+            }
+            WeaponStats::OrbitAndLunge(stats) => {
+                stats.lunge_damage *= might;
+                stats.orbit_radius *= area;
+                stats.lunge_range *= area;
+                stats.satellite_count = add_count(stats.satellite_count, amount);
+                stats.lunge_cooldown *= cooldown;
+            }
+            WeaponStats::DirectionalMelee(stats) => {
+                stats.damage *= might;
+                stats.range *= area;
+                stats.attack_rate /= cooldown;
+            }
+            WeaponStats::TargettedProjectile(stats) => {
+                stats.damage *= might;
+                stats.attack_rate /= cooldown;
+                stats.max_targets = add_count(stats.max_targets, amount);
+                stats.projectile_speed *= projectile_speed;
+                stats.projectile_lifetime *= duration;
+            }
+            WeaponStats::RandomProjectile(stats) => {
+                stats.damage *= might;
+                stats.attack_rate /= cooldown;
+                stats.max_projectiles_per_shot = add_count(stats.max_projectiles_per_shot, amount);
+                stats.projectile_speed *= projectile_speed;
+                stats.projectile_lifetime *= duration;
+            }
+            WeaponStats::AoeAroundPlayer(stats) => {
+                stats.damage *= might;
+                stats.radius *= area;
+                stats.attack_rate /= cooldown;
+                stats.tick_interval = stats.tick_interval.map(|interval| interval * cooldown);
+                stats.duration = stats.duration.map(|value| value * duration);
+            }
+        }
+
+        stats
+    }
+}
+
+impl WeaponDefinition {
+    /// Base stats with every level delta up to and including `level` applied in order.
+    pub fn stats_at(&self, level: u32) -> WeaponStats {
+        let mut deltas: Vec<&LevelDelta> = self
+            .levels
+            .iter()
+            .filter(|delta| delta.level <= level)
+            .collect();
+        deltas.sort_by_key(|delta| delta.level);
+
+        let mut stats = self.stats.clone();
+        for change in deltas.iter().flat_map(|delta| delta.changes.iter()) {
+            if !stats.apply(change) {
+                warn!("{:?} has no stat {:?}, ignoring", self.kind, change.stat());
+            }
+        }
+
+        stats
+    }
+}
+
+/// Looks weapon definitions up from the loaded catalog.
+#[derive(SystemParam)]
+pub struct WeaponDefinitions<'w> {
+    weapon_assets: Option<Res<'w, WeaponAssets>>,
+    catalogs: Res<'w, Assets<WeaponCatalog>>,
+}
+
+impl WeaponDefinitions<'_> {
+    pub fn get(&self, kind: WeaponKind) -> Option<&WeaponDefinition> {
+        let catalog = self.catalogs.get(&self.weapon_assets.as_ref()?.catalog)?;
+        catalog
+            .weapons
+            .iter()
+            .find(|definition| definition.kind == kind)
+    }
+
+    pub fn stats_at(&self, kind: WeaponKind, level: u32) -> Option<WeaponStats> {
+        self.get(kind).map(|definition| definition.stats_at(level))
+    }
+
+    /// Stats of `kind` at `level` once `modifiers` are applied, if any.
+    pub fn final_stats(
+        &self,
+        kind: WeaponKind,
+        level: u32,
+        modifiers: Option<&StatModifiers>,
+    ) -> Option<WeaponStats> {
+        let stats = self.stats_at(kind, level)?;
+        Some(match modifiers {
+            Some(modifiers) => stats.with_modifiers(modifiers),
+            None => stats,
+        })
+    }
+}
@@ -1,10 +1,25 @@
+use bevy_common_assets::ron::RonAssetPlugin;
+
 use crate::prelude::*;
 
-#[butler_plugin]
 #[add_plugin(to_group = EntityPlugins)]
 pub struct WeaponPlugin;
 
+#[butler_plugin]
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<WeaponCatalog>::new(&["weapons.ron"]))
+            .configure_loading_state(
+                LoadingStateConfig::new(GameState::Loading).load_collection::<WeaponAssets>(),
+            );
+    }
+}
+
 pub mod components;
 use crate::weapon::components::*;
 
+pub mod definition;
+use crate::weapon::definition::*;
+
+mod spawner;
 mod systems;
@@ -0,0 +1,146 @@
+use crate::items::StatModifiers;
+use crate::prelude::*;
+use crate::weapon::components::*;
+use crate::weapon::definition::*;
+use crate::weapon::*;
+
+impl WeaponKind {
+    /// Spawns a weapon of this kind at `level`, its stats are filled in from the weapon
+    /// catalog once it is loaded. The caller is responsible for parenting it to its owner
+    /// and adding it to their [`EquippedWeapons`].
+    pub fn spawn(&self, commands: &mut Commands, level: u32) -> Entity {
+        commands
+            .spawn((
+                Name::new(self.name()),
+                *self,
+                WeaponLevel(level),
+                Transform::default(),
+                GlobalTransform::default(),
+            ))
+            .id()
+    }
+}
+
+fn attack_timer(attack_rate: f32) -> Timer {
+    Timer::from_seconds(1.0 / attack_rate.max(0.01), TimerMode::Repeating)
+}
+
+impl WeaponStats {
+    /// Inserts the weapon component described by these stats.
+    pub fn insert(&self, entity: &mut EntityCommands, image: Handle<Image>) {
+        match self.clone() {
+            WeaponStats::Orbit(stats) => entity.insert(OrbitWeapon {
+                orbit_speed: stats.orbit_speed,
+                orbit_radius: stats.orbit_radius,
+                orbit_direction: stats.orbit_direction,
+                satellite_count: stats.satellite_count,
+                satellite_image: image,
+                contact_damage: stats.contact_damage,
+                contact_cooldown_per_entity: stats.contact_cooldown_per_entity,
+            }),
+            WeaponStats::OrbitAndLunge(stats) => entity.insert(OrbitAndLungeWeapon {
+                orbit_speed: stats.orbit_speed,
+                orbit_radius: stats.orbit_radius,
+                orbit_direction: stats.orbit_direction,
+                satellite_count: stats.satellite_count,
+                satellite_image: image,
+                lunge_damage: stats.lunge_damage,
+                lunge_range: stats.lunge_range,
+                lunge_cooldown: stats.lunge_cooldown,
+            }),
+            WeaponStats::DirectionalMelee(stats) => entity.insert(DirectionalMeleeWeapon {
+                damage: stats.damage,
+                range: stats.range,
+                attack_angle_deg: stats.attack_angle_deg,
+                attack_rate: stats.attack_rate,
+                direction_mode: stats.direction_mode,
+                slash_image: image,
+                attack_timer: attack_timer(stats.attack_rate),
+            }),
+            WeaponStats::TargettedProjectile(stats) => entity.insert(TargettedProjectileWeapon {
+                damage: stats.damage,
+                projectile_speed: stats.projectile_speed,
+                projectile_lifetime: stats.projectile_lifetime,
+                attack_rate: stats.attack_rate,
+                max_targets: stats.max_targets,
+                homing: stats.homing,
+                spread_angle_deg: stats.spread_angle_deg,
+                projectile_image: image,
+                attack_timer: attack_timer(stats.attack_rate),
+            }),
+            WeaponStats::RandomProjectile(stats) => entity.insert(RandomProjectileWeapon {
+                damage: stats.damage,
+                projectile_speed: stats.projectile_speed,
+                projectile_lifetime: stats.projectile_lifetime,
+                attack_rate: stats.attack_rate,
+                max_projectiles_per_shot: stats.max_projectiles_per_shot,
+                spread_angle_deg: stats.spread_angle_deg,
+                firing_arc_deg: stats.firing_arc_deg,
+                projectile_image: image,
+                attack_timer: attack_timer(stats.attack_rate),
+            }),
+            WeaponStats::AoeAroundPlayer(stats) => entity.insert(AoeAroundPlayerWeapon {
+                damage: stats.damage,
+                radius: stats.radius,
+                attack_rate: stats.attack_rate,
+                duration: stats.duration,
+                damage_over_time: stats.damage_over_time,
+                tick_interval: stats.tick_interval,
+                attack_timer: attack_timer(stats.attack_rate),
+            }),
+        };
+    }
+}
+
+/// Turns freshly spawned weapons into their concrete component, using the stats of the
+/// level they were spawned at.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+fn build_weapons(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    definitions: WeaponDefinitions,
+    query: Query<(Entity, &WeaponKind, &WeaponLevel, Option<&ChildOf>), Without<Weapon>>,
+    owner_query: Query<&StatModifiers>,
+) {
+    for (entity, kind, level, child_of) in query.iter() {
+        let Some(definition) = definitions.get(*kind) else {
+            warn_once!("No definition for weapon {:?}", kind);
+            continue;
+        };
+
+        let mut stats = definition.stats_at(level.0);
+        if let Some(modifiers) =
+            child_of.and_then(|child_of| owner_query.get(child_of.parent()).ok())
+        {
+            stats = stats.with_modifiers(modifiers);
+        }
+        if stats.kind() != *kind {
+            warn_once!(
+                "Definition for {:?} has {:?} stats, skipping",
+                kind,
+                stats.kind()
+            );
+            continue;
+        }
+
+        let image = definition
+            .image
+            .as_ref()
+            .map(|path| assets.load(path))
+            .unwrap_or_default();
+        let mut entity = commands.entity(entity);
+        stats.insert(&mut entity, image);
+        if let Some(targeting) = definition.targeting {
+            entity.insert(targeting);
+        }
+        if let Some(on_hit) = definition.on_hit {
+            entity.insert(OnHitEffect(on_hit));
+        }
+        if definition.knockback > 0.0 {
+            entity.insert(KnockbackStrength(definition.knockback));
+        }
+        if definition.crit_chance > 0.0 {
+            entity.insert(CritChance(definition.crit_chance));
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use crate::actor::*;
+use crate::enemy::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+const AURA_COLOR: Color = Color::srgba(0.6, 0.3, 0.9, 0.35);
+const PULSE_COLOR: Color = Color::srgba(1.0, 0.6, 0.2, 0.8);
+
+#[add_system(schedule = Startup, plugin = WeaponPlugin)]
+fn create_aoe_ring_mesh(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.insert_resource(AoeRingMesh(meshes.add(Annulus::new(0.94, 1.0))));
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn spawn_aoe_aura_rings(
+    mut commands: Commands,
+    ring_mesh: Res<AoeRingMesh>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<(Entity, &AoeAroundPlayerWeapon), Added<AoeAroundPlayerWeapon>>,
+) {
+    for (weapon_entity, weapon) in query.iter() {
+        if !weapon.damage_over_time {
+            continue;
+        }
+
+        let ring = commands
+            .spawn((
+                Name::new("Aoe Aura Ring"),
+                AoeAuraRing {
+                    weapon: weapon_entity,
+                },
+                Mesh2d(ring_mesh.0.clone()),
+                MeshMaterial2d(materials.add(AURA_COLOR)),
+                Transform::from_xyz(0.0, 0.0, -0.5).with_scale(Vec3::splat(weapon.radius)),
+            ))
+            .id();
+
+        commands.entity(weapon_entity).add_child(ring);
+    }
+}
+
+/// Keeps aura rings matching their weapon's radius as it levels up.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn resize_aoe_aura_rings(
+    mut ring_query: Query<(&AoeAuraRing, &mut Transform)>,
+    weapon_query: Query<&AoeAroundPlayerWeapon, Changed<AoeAroundPlayerWeapon>>,
+) {
+    for (ring, mut transform) in ring_query.iter_mut() {
+        if let Ok(weapon) = weapon_query.get(ring.weapon) {
+            transform.scale = Vec3::splat(weapon.radius);
+        }
+    }
+}
+
+fn live_enemies_in_radius(
+    tree: &EnemyKdTree,
+    enemy_query: &Query<&Health, With<Enemy>>,
+    origin: Vec2,
+    radius: f32,
+) -> Vec<Entity> {
+    tree.0
+        .within_radius(&[origin.x, origin.y], radius)
+        .into_iter()
+        .map(|collision| collision.entity)
+        .filter(|entity| {
+            enemy_query
+                .get(*entity)
+                .is_ok_and(|health| health.current > 0.0)
+        })
+        .collect()
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn tick_aoe_auras(
+    tree: Res<EnemyKdTree>,
+    mut weapon_query: Query<(
+        Entity,
+        &AoeAroundPlayerWeapon,
+        &mut HitCooldowns,
+        &GlobalTransform,
+    )>,
+    enemy_query: Query<&Health, With<Enemy>>,
+    mut hits: WeaponHits,
+) {
+    for (weapon_entity, weapon, mut hit_cooldowns, weapon_global) in weapon_query.iter_mut() {
+        if !weapon.damage_over_time {
+            continue;
+        }
+
+        let interval = weapon.tick_interval();
+        let origin = weapon_global.translation().truncate();
+        for enemy in live_enemies_in_radius(&tree, &enemy_query, origin, weapon.radius) {
+            if hit_cooldowns.try_hit(enemy, interval) {
+                hits.write(weapon_entity, enemy, weapon.damage);
+            }
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn fire_aoe_pulses(
+    mut commands: Commands,
+    time: Res<Time>,
+    tree: Res<EnemyKdTree>,
+    ring_mesh: Res<AoeRingMesh>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut weapon_query: Query<(Entity, &mut AoeAroundPlayerWeapon, &GlobalTransform)>,
+    enemy_query: Query<&Health, With<Enemy>>,
+    mut hits: WeaponHits,
+) {
+    for (weapon_entity, mut weapon, weapon_global) in weapon_query.iter_mut() {
+        if weapon.damage_over_time {
+            continue;
+        }
+
+        let period = Duration::from_secs_f32(1.0 / weapon.attack_rate.max(0.01));
+        if weapon.attack_timer.duration() != period {
+            weapon.attack_timer.set_duration(period);
+        }
+
+        weapon.attack_timer.tick(time.delta());
+        if !weapon.attack_timer.just_finished() {
+            continue;
+        }
+
+        let origin = weapon_global.translation().truncate();
+        for enemy in live_enemies_in_radius(&tree, &enemy_query, origin, weapon.radius) {
+            hits.write(weapon_entity, enemy, weapon.damage);
+        }
+
+        let ring = commands
+            .spawn((
+                Name::new("Aoe Pulse Ring"),
+                AoePulseRing {
+                    radius: weapon.radius,
+                    timer: Timer::from_seconds(weapon.pulse_duration(), TimerMode::Once),
+                },
+                Mesh2d(ring_mesh.0.clone()),
+                MeshMaterial2d(materials.add(PULSE_COLOR)),
+                Transform::from_xyz(0.0, 0.0, -0.5).with_scale(Vec3::ZERO),
+            ))
+            .id();
+
+        commands.entity(weapon_entity).add_child(ring);
+    }
+}
+
+/// Expands pulse rings out to their radius, fading them out as they go.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn animate_aoe_pulses(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(
+        Entity,
+        &mut AoePulseRing,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+) {
+    for (entity, mut pulse, mut transform, material) in query.iter_mut() {
+        pulse.timer.tick(time.delta());
+        if pulse.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = pulse.timer.fraction();
+        transform.scale = Vec3::splat(pulse.radius * progress);
+        if let Some(material) = materials.get_mut(&material.0) {
+            material
+                .color
+                .set_alpha(PULSE_COLOR.alpha() * (1.0 - progress));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use crate::actor::*;
+use crate::enemy::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+const SLASH_DURATION: f32 = 0.15;
+
+fn in_cone(origin: Vec2, direction: Vec2, half_angle: f32, point: Vec2) -> bool {
+    let to_point = point - origin;
+    if to_point.length_squared() <= f32::EPSILON {
+        return true;
+    }
+
+    direction.angle_to(to_point).abs() <= half_angle
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn attack_directional_melee_weapons(
+    mut commands: Commands,
+    time: Res<Time>,
+    tree: Res<EnemyKdTree>,
+    mut weapon_query: Query<(
+        Entity,
+        &mut DirectionalMeleeWeapon,
+        &GlobalTransform,
+        &ChildOf,
+    )>,
+    owner_query: Query<&Facing>,
+    enemy_query: Query<(&GlobalTransform, &Health), With<Enemy>>,
+    mut hits: WeaponHits,
+) {
+    for (weapon_entity, mut weapon, weapon_global, child_of) in weapon_query.iter_mut() {
+        let period = Duration::from_secs_f32(1.0 / weapon.attack_rate.max(0.01));
+        if weapon.attack_timer.duration() != period {
+            weapon.attack_timer.set_duration(period);
+        }
+
+        weapon.attack_timer.tick(time.delta());
+        if !weapon.attack_timer.just_finished() {
+            continue;
+        }
+
+        let facing = owner_query
+            .get(child_of.parent())
+            .map(|facing| facing.0)
+            .unwrap_or(Vec2::X);
+        let direction = weapon.direction_mode.resolve(facing);
+        let half_angle = weapon.attack_angle_deg.to_radians() / 2.0;
+
+        let origin = weapon_global.translation().truncate();
+        let nearby_enemies = tree.0.within_radius(&[origin.x, origin.y], weapon.range);
+
+        for enemy_collision in nearby_enemies {
+            let Ok((enemy_transform, health)) = enemy_query.get(enemy_collision.entity) else {
+                continue;
+            };
+
+            if health.current <= 0.0 {
+                continue;
+            }
+
+            if in_cone(
+                origin,
+                direction,
+                half_angle,
+                enemy_transform.translation().truncate(),
+            ) {
+                hits.write(weapon_entity, enemy_collision.entity, weapon.damage);
+            }
+        }
+
+        let slash = commands
+            .spawn((
+                Name::new("Slash"),
+                Slash {
+                    direction,
+                    half_angle,
+                    radius: weapon.range * 0.6,
+                    timer: Timer::from_seconds(SLASH_DURATION, TimerMode::Once),
+                },
+                Sprite {
+                    image: weapon.slash_image.clone(),
+                    ..default()
+                },
+                Transform::default(),
+                GlobalTransform::default(),
+            ))
+            .id();
+
+        commands.entity(weapon_entity).add_child(slash);
+    }
+}
+
+/// Sweeps the slash sprite across the attack arc, fading it out as it goes.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn animate_slashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Slash, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut slash, mut transform, mut sprite) in query.iter_mut() {
+        slash.timer.tick(time.delta());
+        if slash.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = slash.timer.fraction();
+        let sweep = -slash.half_angle + progress * slash.half_angle * 2.0;
+        let direction = Vec2::from_angle(sweep).rotate(slash.direction);
+
+        transform.translation = (direction * slash.radius).extend(1.0);
+        transform.rotation =
+            Quat::from_rotation_z(direction.to_angle() - std::f32::consts::FRAC_PI_2);
+        sprite.color.set_alpha(1.0 - progress);
+    }
+}
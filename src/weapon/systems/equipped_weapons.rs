@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use crate::weapon::*;
+
+/// Despawns weapons that were taken out of their owner's [`EquippedWeapons`], satellites
+/// and anything else parented to them go along with them.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+fn despawn_unequipped_weapons(
+    mut commands: Commands,
+    owner_query: Query<(Entity, &EquippedWeapons, &Children), Changed<EquippedWeapons>>,
+    weapon_query: Query<(), With<WeaponKind>>,
+) {
+    for (owner, equipped, children) in owner_query.iter() {
+        for child in children.iter() {
+            if weapon_query.contains(child) && !equipped.0.contains(&child) {
+                info!("Unequipping weapon {} from {}", child, owner);
+                commands.entity(child).despawn();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unequipping_despawns_the_weapon_and_its_satellites() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, despawn_unequipped_weapons);
+
+        let weapon = app.world_mut().spawn(WeaponKind::Orbit).id();
+        let satellites: Vec<Entity> = (0..3)
+            .map(|_| app.world_mut().spawn(ChildOf(weapon)).id())
+            .collect();
+        let owner = app
+            .world_mut()
+            .spawn(EquippedWeapons(vec![weapon]))
+            .add_child(weapon)
+            .id();
+
+        app.update();
+        assert!(app.world().get_entity(weapon).is_ok());
+
+        app.world_mut()
+            .get_mut::<EquippedWeapons>(owner)
+            .unwrap()
+            .0
+            .clear();
+        app.update();
+
+        assert!(app.world().get_entity(owner).is_ok());
+        assert!(app.world().get_entity(weapon).is_err());
+        for satellite in satellites {
+            assert!(app.world().get_entity(satellite).is_err());
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::ecs::entity::Entities;
+
+use crate::prelude::*;
+use crate::weapon::*;
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn tick_hit_cooldowns(time: Res<Time>, entities: &Entities, mut query: Query<&mut HitCooldowns>) {
+    for mut cooldowns in query.iter_mut() {
+        if cooldowns.is_empty() {
+            continue;
+        }
+
+        cooldowns.tick(time.delta(), |target| entities.contains(target));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::actor::*;
+use crate::enemy::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn apply_knockback(
+    mut damaged: EventReader<Damaged>,
+    weapon_query: Query<(&KnockbackStrength, &GlobalTransform)>,
+    mut enemy_query: Query<(&GlobalTransform, &EnemyArchetype, &mut Knockback), With<Enemy>>,
+) {
+    for event in damaged.read() {
+        if event.over_time {
+            continue;
+        }
+
+        let Ok((strength, weapon_transform)) = weapon_query.get(event.source) else {
+            continue;
+        };
+        let Ok((enemy_transform, archetype, mut knockback)) = enemy_query.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        let away = (enemy_transform.translation() - weapon_transform.translation())
+            .truncate()
+            .normalize_or_zero()
+            .extend(0.0);
+        let resistance = archetype.knockback_resistance().clamp(0.0, 1.0);
+        knockback.apply_impulse(away * strength.0 * (1.0 - resistance));
+    }
+}
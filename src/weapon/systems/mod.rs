@@ -1,2 +1,14 @@
+mod aoe_around_player_weapon;
+mod directional_melee_weapon;
+mod equipped_weapons;
+mod hit_cooldowns;
+mod knockback;
+mod on_hit_effect;
 mod orbit_and_lunge_weapon;
 mod orbit_weapon;
+mod projectile;
+mod random_projectile_weapon;
+mod targeting;
+mod targetted_projectile_weapon;
+mod weapon_rng;
+mod weapon_stats;
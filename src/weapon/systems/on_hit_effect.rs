@@ -0,0 +1,24 @@
+use crate::actor::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn apply_on_hit_effects(
+    mut damaged: EventReader<Damaged>,
+    weapon_query: Query<&OnHitEffect>,
+    mut effects: EventWriter<ApplyStatusEffect>,
+) {
+    for event in damaged.read() {
+        if event.over_time {
+            continue;
+        }
+
+        if let Ok(on_hit) = weapon_query.get(event.source) {
+            effects.write(ApplyStatusEffect {
+                source: event.source,
+                target: event.target,
+                kind: on_hit.0,
+            });
+        }
+    }
+}
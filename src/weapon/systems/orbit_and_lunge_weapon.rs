@@ -1,61 +1,108 @@
-use bevy::platform::collections::HashSet;
+use bevy::platform::collections::{HashMap, HashSet};
 
 use crate::actor::Health;
 use crate::enemy::Enemy;
 use crate::enemy::EnemyKdTree;
 use crate::prelude::*;
+use crate::weapon::systems::targeting::{TargetQuery, select_targets};
 use crate::weapon::*;
 
-#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
-fn spawn_orbit_and_lunge_satellites(
+fn orbit_and_lunge_satellite(
+    weapon_entity: Entity,
+    weapon: &OrbitAndLungeWeapon,
+    index: usize,
+) -> impl Bundle + use<> {
+    (
+        Name::new(format!("Orbit and Lunge Weapon Satellite {}", index)),
+        OrbitAndLungeSatellite {
+            index,
+            weapon: weapon_entity,
+            state: LungeState::Idle,
+            progress: 0.0,
+            cooldown_timer: Timer::from_seconds(weapon.lunge_cooldown, TimerMode::Once),
+            lunge_target: None,
+            target: None,
+            sweep_from: None,
+        },
+        Sprite {
+            image: weapon.satellite_image.clone(),
+            ..default()
+        },
+        // ShowAabbGizmo {
+        //     color: Some(Color::srgb(1.0, 0.0, 1.0)),
+        // },
+        Transform::default(),
+        GlobalTransform::default(),
+    )
+}
+
+/// Spawns or despawns satellites to match `satellite_count` whenever the weapon changes,
+/// then spaces the survivors evenly again. Satellites mid lunge keep lunging.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn reconcile_orbit_and_lunge_satellites(
     mut commands: Commands,
-    query: Query<(Entity, &OrbitAndLungeWeapon), Added<OrbitAndLungeWeapon>>,
+    weapon_query: Query<(Entity, &OrbitAndLungeWeapon), Changed<OrbitAndLungeWeapon>>,
+    mut satellite_query: Query<(Entity, &mut OrbitAndLungeSatellite)>,
 ) {
-    for (weapon_entity, weapon_data) in query.iter() {
-        info!(
-            "Adding orbit and lunge satellites for weapon: {}",
-            weapon_entity
-        );
-        for i in 0..weapon_data.satellite_count {
+    for (weapon_entity, weapon) in weapon_query.iter() {
+        let mut satellites: Vec<_> = satellite_query
+            .iter_mut()
+            .filter(|(_, satellite)| satellite.weapon == weapon_entity)
+            .collect();
+        satellites.sort_by_key(|(_, satellite)| satellite.index);
+
+        let existing = satellites.len();
+        if existing != weapon.satellite_count {
+            info!(
+                "Reconciling orbit and lunge satellites for weapon {}: {} -> {}",
+                weapon_entity, existing, weapon.satellite_count
+            );
+        }
+
+        for (index, (entity, mut satellite)) in satellites.into_iter().enumerate() {
+            if index >= weapon.satellite_count {
+                commands.entity(entity).despawn();
+            } else if satellite.index != index {
+                satellite.index = index;
+            }
+        }
+
+        for index in existing..weapon.satellite_count {
             let satellite = commands
-                .spawn((
-                    Name::new(format!("Orbit and Lunge Weapon Satellite {}", i)),
-                    OrbitAndLungeSatellite {
-                        index: i,
-                        weapon: weapon_entity,
-                        state: LungeState::Idle,
-                        progress: 0.0,
-                        cooldown_timer: Timer::from_seconds(
-                            weapon_data.lunge_cooldown,
-                            TimerMode::Once,
-                        ),
-                        lunge_target: None,
-                        hit_entities_this_lunge: HashSet::new(),
-                    },
-                    Sprite {
-                        image: weapon_data.satellite_image.clone(),
-                        ..default()
-                    },
-                    // ShowAabbGizmo {
-                    //     color: Some(Color::srgb(1.0, 0.0, 1.0)),
-                    // },
-                    Transform::default(),
-                    GlobalTransform::default(),
-                ))
+                .spawn(orbit_and_lunge_satellite(weapon_entity, weapon, index))
                 .id();
-
             commands.entity(weapon_entity).add_child(satellite);
         }
     }
 }
 
-#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+/// Satellites are children of their weapon so despawning it takes them along, this covers
+/// the weapon component being removed from an entity that stays around.
+#[add_observer(plugin = WeaponPlugin)]
+fn despawn_orbit_and_lunge_satellites(
+    trigger: Trigger<OnRemove, OrbitAndLungeWeapon>,
+    mut commands: Commands,
+    satellite_query: Query<(Entity, &OrbitAndLungeSatellite)>,
+) {
+    for (entity, satellite) in satellite_query.iter() {
+        if satellite.weapon == trigger.target() {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
 fn update_orbit_and_lunge_satellites(
     time: Res<Time>,
     tree: Res<EnemyKdTree>,
-    enemy_query: Query<&GlobalTransform, With<Enemy>>,
-    mut query: Query<(&mut Transform, &mut OrbitAndLungeSatellite)>,
-    weapon_query: Query<(&OrbitAndLungeWeapon, &GlobalTransform)>,
+    mut rng: ResMut<WeaponRng>,
+    enemy_query: TargetQuery,
+    mut query: Query<(
+        &mut Transform,
+        &mut OrbitAndLungeSatellite,
+        &mut HitCooldowns,
+    )>,
+    weapon_query: Query<(&OrbitAndLungeWeapon, &TargetingStrategy, &GlobalTransform)>,
 ) {
     fn rotation_towards(from_world: Vec3, to_world: Vec3) -> Quat {
         let dir = (to_world - from_world).truncate();
@@ -66,9 +113,18 @@ fn update_orbit_and_lunge_satellites(
         }
     }
 
-    for (mut transform, mut satellite) in query.iter_mut() {
-        if let Ok((weapon, weapon_global)) = weapon_query.get(satellite.weapon) {
+    // Enemies already being lunged at, per weapon, so satellites spread out over targets
+    let mut claimed: HashMap<Entity, HashSet<Entity>> = HashMap::new();
+    for (_, satellite, _) in query.iter() {
+        if let Some(target) = satellite.target {
+            claimed.entry(satellite.weapon).or_default().insert(target);
+        }
+    }
+
+    for (mut transform, mut satellite, mut hit_cooldowns) in query.iter_mut() {
+        if let Ok((weapon, strategy, weapon_global)) = weapon_query.get(satellite.weapon) {
             satellite.cooldown_timer.tick(time.delta());
+            satellite.sweep_from = None;
 
             let dir_factor = match weapon.orbit_direction {
                 OrbitDirection::Clockwise => -1.0,
@@ -92,27 +148,33 @@ fn update_orbit_and_lunge_satellites(
 
             match satellite.state {
                 LungeState::Idle => {
-                    let pos_2d = [orbit_pos_world.x, orbit_pos_world.y];
-                    let nearby_enemies = tree.0.within_radius(&pos_2d, weapon.lunge_range);
-
-                    let nearest_enemy_pos = nearby_enemies
-                        .iter()
-                        .filter_map(|enemy_collision| enemy_query.get(enemy_collision.entity).ok())
-                        .min_by_key(|enemy_tf| {
-                            let enemy_pos = enemy_tf.translation();
-                            ((enemy_pos.x - orbit_pos_world.x).powi(2)
-                                + (enemy_pos.y - orbit_pos_world.y).powi(2))
-                            .sqrt()
-                            .to_bits()
-                        })
-                        .map(|enemy_tf| enemy_tf.translation());
-
-                    if let Some(target_pos_world) = nearest_enemy_pos {
+                    let weapon_claims = claimed.entry(satellite.weapon).or_default();
+                    let target = select_targets(
+                        *strategy,
+                        &tree,
+                        &enemy_query,
+                        orbit_pos_world.truncate(),
+                        weapon.lunge_range,
+                        1,
+                        weapon_claims,
+                        &mut rng.0,
+                    )
+                    .first()
+                    .and_then(|target| {
+                        enemy_query
+                            .get(*target)
+                            .ok()
+                            .map(|(transform, ..)| (*target, transform.translation()))
+                    });
+
+                    if let Some((target, target_pos_world)) = target {
+                        weapon_claims.insert(target);
+                        satellite.target = Some(target);
                         satellite.lunge_target = Some(target_pos_world);
                         satellite.state = LungeState::LungingOut;
                         satellite.progress = 0.0;
                         satellite.cooldown_timer.reset();
-                        satellite.hit_entities_this_lunge.clear();
+                        hit_cooldowns.clear();
                     } else {
                         transform.translation = local_orbit_offset;
                         transform.rotation = rotation_towards(orbit_pos_world, weapon_pos_world);
@@ -128,10 +190,12 @@ fn update_orbit_and_lunge_satellites(
 
                         let lunge_pos_world =
                             orbit_pos_world.lerp(target_pos_world, satellite.progress);
+                        satellite.sweep_from = Some(weapon_pos_world + transform.translation);
                         transform.translation = lunge_pos_world - weapon_pos_world;
                         transform.rotation = rotation_towards(lunge_pos_world, target_pos_world);
                     } else {
                         satellite.state = LungeState::Idle;
+                        satellite.target = None;
                     }
                 }
                 LungeState::Returning => {
@@ -141,6 +205,7 @@ fn update_orbit_and_lunge_satellites(
                             satellite.progress = 0.0;
                             satellite.state = LungeState::Idle;
                             satellite.lunge_target = None;
+                            satellite.target = None;
                             satellite.cooldown_timer.reset();
                         }
 
@@ -150,6 +215,7 @@ fn update_orbit_and_lunge_satellites(
                         transform.rotation = rotation_towards(return_pos_world, weapon_pos_world);
                     } else {
                         satellite.state = LungeState::Idle;
+                        satellite.target = None;
                     }
                 }
             }
@@ -157,52 +223,58 @@ fn update_orbit_and_lunge_satellites(
     }
 }
 
-#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+/// Sweeps each lunging satellite from where it was to where it is this frame, so a lunge
+/// can't skip over enemies no matter how far it travels in one frame.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing), after = update_orbit_and_lunge_satellites)]
 fn apply_orbit_and_lunge_weapon_damage(
     tree: Res<EnemyKdTree>,
-    mut weapon_query: Query<&mut OrbitAndLungeWeapon>,
-    mut enemy_query: Query<&mut Health, With<Enemy>>,
+    weapon_query: Query<(&OrbitAndLungeWeapon, &GlobalTransform)>,
+    enemy_query: Query<&Health, With<Enemy>>,
     mut satellite_query: Query<(
-        &GlobalTransform,
+        &Transform,
         &Aabb,
-        &mut OrbitAndLungeSatellite,
-        &GlobalTransform,
+        &OrbitAndLungeSatellite,
+        &mut HitCooldowns,
     )>,
+    mut hits: WeaponHits,
 ) {
-    for (global_transform, aabb, mut satellite, weapon_parent_global_transform) in
-        satellite_query.iter_mut()
-    {
-        if satellite.state != LungeState::LungingOut {
+    for (transform, aabb, satellite, mut hit_cooldowns) in satellite_query.iter_mut() {
+        let Some(sweep_from) = satellite.sweep_from else {
             continue;
-        }
+        };
 
-        let Ok(weapon) = weapon_query.get_mut(satellite.weapon) else {
+        let Ok((weapon, weapon_global)) = weapon_query.get(satellite.weapon) else {
             continue;
         };
 
-        let sat_pos = global_transform.translation();
-        let sat_pos_2d = [sat_pos.x, sat_pos.y];
+        // Global transforms aren't propagated until after Update, work from the local one
+        let sat_pos = weapon_global.translation() + transform.translation;
         let radius = aabb.half_extents.max_element();
 
-        let nearby_enemies = tree.0.within_radius(&sat_pos_2d, radius);
+        let nearby_enemies = tree.within_capsule(sweep_from.truncate(), sat_pos.truncate(), radius);
 
         for enemy_collision in nearby_enemies {
             let enemy_entity = enemy_collision.entity;
 
-            if satellite.hit_entities_this_lunge.contains(&enemy_entity) {
+            if !hit_cooldowns.can_hit(enemy_entity) {
                 continue;
             }
 
-            if let Ok(mut health) = enemy_query.get_mut(enemy_entity) {
-                health.current -= weapon.lunge_damage;
+            if let Ok(health) = enemy_query.get(enemy_entity) {
+                if health.current <= 0.0 {
+                    continue;
+                }
+
+                hits.write(satellite.weapon, enemy_entity, weapon.lunge_damage);
 
-                satellite.hit_entities_this_lunge.insert(enemy_entity);
+                // A full lunge out and back, cleared early when the next lunge starts
+                hit_cooldowns.register(enemy_entity, weapon.lunge_cooldown);
             }
         }
     }
 }
 
-// #[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+// #[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
 // fn draw_lunge_ranges(
 //     mut gizmos: Gizmos,
 //     satellite_query: Query<(&GlobalTransform, &OrbitAndLungeSatellite)>,
@@ -218,3 +290,106 @@ fn apply_orbit_and_lunge_weapon_damage(
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+    use kd_tree::KdTree;
+
+    use super::*;
+    use crate::actor::DamageEvent;
+    use crate::enemy::Collision;
+
+    #[derive(Resource, Default)]
+    struct Hits(Vec<Entity>);
+
+    fn record_hits(mut events: EventReader<DamageEvent>, mut hits: ResMut<Hits>) {
+        hits.0.extend(events.read().map(|event| event.target));
+    }
+
+    #[test]
+    fn lunges_hit_enemies_skipped_over_at_low_frame_rates() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin))
+            // 5 FPS, a lunge out covers 320 of its 400 units in a single frame
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                200,
+            )))
+            .init_resource::<Hits>()
+            .init_resource::<WeaponRng>()
+            .add_event::<DamageEvent>()
+            .add_systems(
+                Update,
+                (
+                    update_orbit_and_lunge_satellites,
+                    apply_orbit_and_lunge_weapon_damage,
+                    record_hits,
+                )
+                    .chain(),
+            );
+
+        let enemy_position = Vec2::new(150.0, 0.0);
+        let enemy = app.world_mut().spawn((Enemy, Health::default())).id();
+        app.insert_resource(EnemyKdTree(KdTree::build_by_ordered_float(vec![
+            Collision {
+                pos: enemy_position,
+                entity: enemy,
+            },
+        ])));
+
+        let weapon = app
+            .world_mut()
+            .spawn((
+                OrbitAndLungeWeapon {
+                    orbit_speed: 0.0,
+                    orbit_radius: 0.0,
+                    orbit_direction: OrbitDirection::Clockwise,
+                    satellite_count: 1,
+                    satellite_image: Handle::default(),
+                    lunge_damage: 10.0,
+                    lunge_range: 64.0,
+                    lunge_cooldown: 0.5,
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        let bundle = orbit_and_lunge_satellite(
+            weapon,
+            app.world().get::<OrbitAndLungeWeapon>(weapon).unwrap(),
+            0,
+        );
+        let satellite = app
+            .world_mut()
+            .spawn((
+                bundle,
+                Aabb::from_min_max(Vec3::splat(-8.0), Vec3::splat(8.0)),
+            ))
+            .id();
+        app.world_mut().entity_mut(weapon).add_child(satellite);
+
+        let mut lunge = app
+            .world_mut()
+            .get_mut::<OrbitAndLungeSatellite>(satellite)
+            .unwrap();
+        lunge.state = LungeState::LungingOut;
+        lunge.lunge_target = Some(Vec3::new(400.0, 0.0, 0.0));
+
+        let mut positions = Vec::new();
+        for _ in 0..4 {
+            app.update();
+            positions.push(app.world().get::<Transform>(satellite).unwrap().translation);
+        }
+
+        assert!(
+            positions
+                .iter()
+                .all(|position| position.truncate().distance(enemy_position) > 8.0),
+            "satellite landed on the enemy, the frame rate isn't low enough: {:?}",
+            positions
+        );
+        assert_eq!(app.world().resource::<Hits>().0, vec![enemy]);
+    }
+}
@@ -3,39 +3,85 @@ use crate::enemy::*;
 use crate::prelude::*;
 use crate::weapon::*;
 
-#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
-fn spawn_orbit_satellites(
+fn orbit_satellite(
+    weapon_entity: Entity,
+    weapon: &OrbitWeapon,
+    index: usize,
+) -> impl Bundle + use<> {
+    (
+        Name::new(format!("Orbit Weapon Satellite {}", index)),
+        OrbitWeaponSatellite {
+            index,
+            weapon: weapon_entity,
+        },
+        Sprite {
+            image: weapon.satellite_image.clone(),
+            ..default()
+        },
+        // ShowAabbGizmo {
+        //     color: Some(Color::srgb(1.0, 0.0, 1.0)),
+        // },
+        Transform::default(),
+        GlobalTransform::default(),
+    )
+}
+
+/// Spawns or despawns satellites to match `satellite_count` whenever the weapon changes,
+/// then spaces the survivors evenly again.
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn reconcile_orbit_satellites(
     mut commands: Commands,
-    query: Query<(Entity, &OrbitWeapon), Added<OrbitWeapon>>,
+    weapon_query: Query<(Entity, &OrbitWeapon), Changed<OrbitWeapon>>,
+    mut satellite_query: Query<(Entity, &mut OrbitWeaponSatellite)>,
 ) {
-    for (weapon_entity, weapon_data) in query.iter() {
-        info!("Adding orbit satellites for weapon: {}", weapon_entity);
-        for i in 0..weapon_data.satellite_count {
+    for (weapon_entity, weapon) in weapon_query.iter() {
+        let mut satellites: Vec<_> = satellite_query
+            .iter_mut()
+            .filter(|(_, satellite)| satellite.weapon == weapon_entity)
+            .collect();
+        satellites.sort_by_key(|(_, satellite)| satellite.index);
+
+        let existing = satellites.len();
+        if existing != weapon.satellite_count {
+            info!(
+                "Reconciling orbit satellites for weapon {}: {} -> {}",
+                weapon_entity, existing, weapon.satellite_count
+            );
+        }
+
+        for (index, (entity, mut satellite)) in satellites.into_iter().enumerate() {
+            if index >= weapon.satellite_count {
+                commands.entity(entity).despawn();
+            } else if satellite.index != index {
+                satellite.index = index;
+            }
+        }
+
+        for index in existing..weapon.satellite_count {
             let satellite = commands
-                .spawn((
-                    Name::new(format!("Orbit Weapon Satellite {}", i)),
-                    OrbitWeaponSatellite {
-                        index: i,
-                        weapon: weapon_entity,
-                    },
-                    Sprite {
-                        image: weapon_data.satellite_image.clone(),
-                        ..default()
-                    },
-                    // ShowAabbGizmo {
-                    //     color: Some(Color::srgb(1.0, 0.0, 1.0)),
-                    // },
-                    Transform::default(),
-                    GlobalTransform::default(),
-                ))
+                .spawn(orbit_satellite(weapon_entity, weapon, index))
                 .id();
-
             commands.entity(weapon_entity).add_child(satellite);
         }
     }
 }
 
-#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+/// Satellites are children of their weapon so despawning it takes them along, this covers
+/// the weapon component being removed from an entity that stays around.
+#[add_observer(plugin = WeaponPlugin)]
+fn despawn_orbit_satellites(
+    trigger: Trigger<OnRemove, OrbitWeapon>,
+    mut commands: Commands,
+    satellite_query: Query<(Entity, &OrbitWeaponSatellite)>,
+) {
+    for (entity, satellite) in satellite_query.iter() {
+        if satellite.weapon == trigger.target() {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
 fn update_orbit_satellites(
     time: Res<Time>,
     mut query: Query<(&mut Transform, &OrbitWeaponSatellite)>,
@@ -66,15 +112,16 @@ fn update_orbit_satellites(
     }
 }
 
-#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(GameState::InGame))]
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
 fn apply_orbit_weapon_damage(
     tree: Res<EnemyKdTree>,
     satellite_query: Query<(&GlobalTransform, &Aabb, &OrbitWeaponSatellite)>,
-    mut weapon_query: Query<&mut OrbitWeapon>,
-    mut enemy_query: Query<&mut Health, With<Enemy>>,
+    mut weapon_query: Query<(&OrbitWeapon, &mut HitCooldowns)>,
+    enemy_query: Query<&Health, With<Enemy>>,
+    mut hits: WeaponHits,
 ) {
     for (global_transform, aabb, satellite) in satellite_query.iter() {
-        if let Ok(mut weapon) = weapon_query.get_mut(satellite.weapon) {
+        if let Ok((weapon, mut hit_cooldowns)) = weapon_query.get_mut(satellite.weapon) {
             let cooldown = weapon.contact_cooldown_per_entity;
 
             let pos = global_transform.translation();
@@ -88,22 +135,18 @@ fn apply_orbit_weapon_damage(
                 let enemy_entity = enemy_collision.entity;
 
                 // Check cooldown per enemy
-                let on_cooldown = weapon
-                    .contact_cooldown_map
-                    .get(&enemy_entity)
-                    .map(|timer| !timer.finished())
-                    .unwrap_or(false);
-
-                if on_cooldown {
+                if !hit_cooldowns.can_hit(enemy_entity) {
                     continue;
                 }
 
-                if let Ok(mut health) = enemy_query.get_mut(enemy_entity) {
-                    health.current -= weapon.contact_damage;
+                if let Ok(health) = enemy_query.get(enemy_entity) {
+                    if health.current <= 0.0 {
+                        continue;
+                    }
+
+                    hits.write(satellite.weapon, enemy_entity, weapon.contact_damage);
 
-                    weapon
-                        .contact_cooldown_map
-                        .insert(enemy_entity, Timer::from_seconds(cooldown, TimerMode::Once));
+                    hit_cooldowns.register(enemy_entity, cooldown);
                 }
             }
         }
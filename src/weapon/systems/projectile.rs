@@ -0,0 +1,188 @@
+use crate::actor::*;
+use crate::enemy::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+/// Returns up to `count` of the closest enemies to `position` that are still alive.
+pub(crate) fn nearest_live_enemies(
+    tree: &EnemyKdTree,
+    enemy_query: &Query<(&GlobalTransform, &Health), With<Enemy>>,
+    position: Vec2,
+    count: usize,
+) -> Vec<Entity> {
+    // Ask for a few extra in case some of the nearest are already dead
+    tree.0
+        .nearests(&[position.x, position.y], count * 2)
+        .into_iter()
+        .map(|found| found.item.entity)
+        .filter(|entity| {
+            enemy_query
+                .get(*entity)
+                .is_ok_and(|(_, health)| health.current > 0.0)
+        })
+        .take(count)
+        .collect()
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn move_projectiles(
+    time: Res<Time>,
+    tree: Res<EnemyKdTree>,
+    mut query: Query<(&mut Projectile, &mut Transform)>,
+    enemy_query: Query<(&GlobalTransform, &Health), With<Enemy>>,
+) {
+    for (mut projectile, mut transform) in query.iter_mut() {
+        let position = transform.translation.truncate();
+
+        if let Some(mut homing) = projectile.homing {
+            let target_alive = homing.target.is_some_and(|target| {
+                enemy_query
+                    .get(target)
+                    .is_ok_and(|(_, health)| health.current > 0.0)
+            });
+
+            if !target_alive {
+                homing.target = nearest_live_enemies(&tree, &enemy_query, position, 1)
+                    .first()
+                    .copied();
+            }
+
+            if let Some((target_transform, _)) = homing
+                .target
+                .and_then(|target| enemy_query.get(target).ok())
+            {
+                let desired = (target_transform.translation().truncate() - position)
+                    .normalize_or(projectile.direction);
+                let max_turn = homing.turn_rate * time.delta_secs();
+                let turn = projectile
+                    .direction
+                    .angle_to(desired)
+                    .clamp(-max_turn, max_turn);
+
+                projectile.direction = Vec2::from_angle(turn).rotate(projectile.direction);
+            }
+
+            projectile.homing = Some(homing);
+        }
+
+        let movement = projectile.direction * projectile.speed * time.delta_secs();
+        transform.translation.x += movement.x;
+        transform.translation.y += movement.y;
+        transform.rotation =
+            Quat::from_rotation_z(projectile.direction.to_angle() - std::f32::consts::FRAC_PI_2);
+    }
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing), after = move_projectiles)]
+fn apply_projectile_damage(
+    mut commands: Commands,
+    time: Res<Time>,
+    tree: Res<EnemyKdTree>,
+    mut query: Query<(Entity, &mut Projectile, &Transform)>,
+    enemy_query: Query<&Health, With<Enemy>>,
+    mut hits: WeaponHits,
+) {
+    for (entity, mut projectile, transform) in query.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Sweep the whole step rather than testing where the projectile ended up, fast
+        // projectiles would skip straight over enemies at low frame rates otherwise
+        let position = transform.translation.truncate();
+        let sweep_from = position - projectile.direction * projectile.speed * time.delta_secs();
+        let hit = tree
+            .within_capsule(sweep_from, position, projectile.radius + ENEMY_HALF_EXTENT)
+            .into_iter()
+            .filter(|enemy_collision| {
+                enemy_query
+                    .get(enemy_collision.entity)
+                    .is_ok_and(|health| health.current > 0.0)
+            })
+            .min_by(|a, b| {
+                a.pos
+                    .distance_squared(sweep_from)
+                    .total_cmp(&b.pos.distance_squared(sweep_from))
+            });
+
+        if let Some(enemy_collision) = hit {
+            hits.write(projectile.weapon, enemy_collision.entity, projectile.damage);
+
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kd_tree::KdTree;
+
+    use super::*;
+
+    #[test]
+    fn homing_projectiles_retarget_when_their_target_dies() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, move_projectiles);
+
+        let mut spawn_enemy = |x: f32, health: f32| {
+            let entity = app
+                .world_mut()
+                .spawn((
+                    Enemy,
+                    Health {
+                        max: 10.0,
+                        current: health,
+                    },
+                    GlobalTransform::from_xyz(x, 0.0, 0.0),
+                ))
+                .id();
+            (
+                entity,
+                Collision {
+                    pos: Vec2::new(x, 0.0),
+                    entity,
+                },
+            )
+        };
+        let (dead, dead_collision) = spawn_enemy(50.0, 0.0);
+        let (near, near_collision) = spawn_enemy(100.0, 10.0);
+        let (_, far_collision) = spawn_enemy(300.0, 10.0);
+        app.insert_resource(EnemyKdTree(KdTree::build_by_ordered_float(vec![
+            dead_collision,
+            near_collision,
+            far_collision,
+        ])));
+
+        let projectile = app
+            .world_mut()
+            .spawn((
+                Projectile {
+                    weapon: Entity::PLACEHOLDER,
+                    damage: 1.0,
+                    speed: 100.0,
+                    direction: Vec2::X,
+                    radius: 8.0,
+                    lifetime: Timer::from_seconds(1.0, TimerMode::Once),
+                    homing: Some(Homing {
+                        target: Some(dead),
+                        turn_rate: 6.0,
+                    }),
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        app.update();
+
+        let homing = app
+            .world()
+            .get::<Projectile>(projectile)
+            .unwrap()
+            .homing
+            .unwrap();
+        assert_eq!(homing.target, Some(near));
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::actor::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+const PROJECTILE_RADIUS: f32 = 8.0;
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn fire_random_projectile_weapons(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<WeaponRng>,
+    mut weapon_query: Query<(
+        Entity,
+        &mut RandomProjectileWeapon,
+        &GlobalTransform,
+        &ChildOf,
+    )>,
+    owner_query: Query<&Facing>,
+) {
+    for (weapon_entity, mut weapon, weapon_global, child_of) in weapon_query.iter_mut() {
+        let period = Duration::from_secs_f32(1.0 / weapon.attack_rate.max(0.01));
+        if weapon.attack_timer.duration() != period {
+            weapon.attack_timer.set_duration(period);
+        }
+
+        weapon.attack_timer.tick(time.delta());
+        if !weapon.attack_timer.just_finished() {
+            continue;
+        }
+
+        let facing = owner_query
+            .get(child_of.parent())
+            .map(|facing| facing.0)
+            .unwrap_or(Vec2::X);
+        let half_arc = weapon.firing_arc_deg.to_radians() / 2.0;
+        let half_spread = weapon.spread_angle_deg.to_radians() / 2.0;
+        let origin = weapon_global.translation();
+
+        for _ in 0..weapon.max_projectiles_per_shot {
+            let mut angle = 0.0;
+            if half_arc > 0.0 {
+                angle += rng.0.gen_range(-half_arc..=half_arc);
+            }
+            if half_spread > 0.0 {
+                angle += rng.0.gen_range(-half_spread..=half_spread);
+            }
+
+            commands.spawn(
+                Projectile {
+                    weapon: weapon_entity,
+                    damage: weapon.damage,
+                    speed: weapon.projectile_speed,
+                    direction: Vec2::from_angle(angle).rotate(facing),
+                    radius: PROJECTILE_RADIUS,
+                    lifetime: Timer::from_seconds(weapon.projectile_lifetime, TimerMode::Once),
+                    homing: None,
+                }
+                .bundle(weapon.projectile_image.clone(), origin),
+            );
+        }
+    }
+}
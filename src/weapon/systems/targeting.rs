@@ -0,0 +1,293 @@
+use std::cmp::Ordering;
+
+use bevy::platform::collections::HashSet;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::actor::*;
+use crate::enemy::*;
+use crate::prelude::*;
+use crate::weapon::*;
+
+/// Enemies closer than this to a candidate count towards its cluster.
+const CLUSTER_RADIUS: f32 = 64.0;
+
+pub(crate) type TargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GlobalTransform,
+        &'static Health,
+        &'static EnemyRank,
+    ),
+    With<Enemy>,
+>;
+
+struct Candidate {
+    entity: Entity,
+    position: Vec2,
+    distance: f32,
+    health: f32,
+    rank: EnemyRank,
+}
+
+fn by_distance(a: &Candidate, b: &Candidate) -> Ordering {
+    a.distance.total_cmp(&b.distance)
+}
+
+/// Picks up to `count` live enemies within `range` of `origin` using `strategy`, never
+/// returning anything in `exclude`.
+pub(crate) fn select_targets(
+    strategy: TargetingStrategy,
+    tree: &EnemyKdTree,
+    enemy_query: &TargetQuery,
+    origin: Vec2,
+    range: f32,
+    count: usize,
+    exclude: &HashSet<Entity>,
+    rng: &mut impl Rng,
+) -> Vec<Entity> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<Candidate> = tree
+        .0
+        .within_radius(&[origin.x, origin.y], range)
+        .into_iter()
+        .filter(|collision| !exclude.contains(&collision.entity))
+        .filter_map(|collision| {
+            let (transform, health, rank) = enemy_query.get(collision.entity).ok()?;
+            if health.current <= 0.0 {
+                return None;
+            }
+
+            let position = transform.translation().truncate();
+            Some(Candidate {
+                entity: collision.entity,
+                position,
+                distance: position.distance(origin),
+                health: health.current,
+                rank: *rank,
+            })
+        })
+        .collect();
+
+    match strategy {
+        TargetingStrategy::Nearest => candidates.sort_by(by_distance),
+        TargetingStrategy::Farthest => candidates.sort_by(|a, b| by_distance(b, a)),
+        TargetingStrategy::LowestHealth => candidates.sort_by(|a, b| {
+            a.health
+                .total_cmp(&b.health)
+                .then_with(|| by_distance(a, b))
+        }),
+        TargetingStrategy::HighestHealth => candidates.sort_by(|a, b| {
+            b.health
+                .total_cmp(&a.health)
+                .then_with(|| by_distance(a, b))
+        }),
+        TargetingStrategy::Random => candidates.shuffle(rng),
+        TargetingStrategy::DensestCluster => {
+            let neighbours: Vec<usize> = candidates
+                .iter()
+                .map(|candidate| {
+                    candidates
+                        .iter()
+                        .filter(|other| {
+                            other.position.distance_squared(candidate.position)
+                                <= CLUSTER_RADIUS * CLUSTER_RADIUS
+                        })
+                        .count()
+                })
+                .collect();
+
+            let mut order: Vec<usize> = (0..candidates.len()).collect();
+            order.sort_by(|&a, &b| {
+                neighbours[b]
+                    .cmp(&neighbours[a])
+                    .then_with(|| by_distance(&candidates[a], &candidates[b]))
+            });
+
+            return order
+                .into_iter()
+                .take(count)
+                .map(|index| candidates[index].entity)
+                .collect();
+        }
+        TargetingStrategy::EliteFirst => {
+            candidates.sort_by(|a, b| b.rank.cmp(&a.rank).then_with(|| by_distance(a, b)))
+        }
+    }
+
+    candidates
+        .into_iter()
+        .take(count)
+        .map(|candidate| candidate.entity)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+    use kd_tree::KdTree;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    const RANGE: f32 = 500.0;
+
+    struct Field {
+        world: World,
+        tree: EnemyKdTree,
+        near: Entity,
+        weak: Entity,
+        elite: Entity,
+        cluster: [Entity; 3],
+    }
+
+    impl Field {
+        /// A couple of enemies close by, an elite further out and a tight cluster past it,
+        /// along with a dead enemy and one out of range that should never be picked.
+        fn new() -> Self {
+            let mut world = World::new();
+            let mut collisions = Vec::new();
+            let mut spawn = |x: f32, health: f32, rank: EnemyRank| {
+                let entity = world
+                    .spawn((
+                        Enemy,
+                        rank,
+                        Health {
+                            max: 100.0,
+                            current: health,
+                        },
+                        GlobalTransform::from_xyz(x, 0.0, 0.0),
+                    ))
+                    .id();
+                collisions.push(Collision {
+                    pos: Vec2::new(x, 0.0),
+                    entity,
+                });
+                entity
+            };
+
+            let near = spawn(10.0, 50.0, EnemyRank::Normal);
+            let weak = spawn(20.0, 10.0, EnemyRank::Normal);
+            let elite = spawn(100.0, 80.0, EnemyRank::Elite);
+            let cluster = [
+                spawn(200.0, 30.0, EnemyRank::Normal),
+                spawn(210.0, 30.0, EnemyRank::Normal),
+                spawn(220.0, 30.0, EnemyRank::Normal),
+            ];
+            spawn(5.0, 0.0, EnemyRank::Boss);
+            spawn(1000.0, 100.0, EnemyRank::Boss);
+
+            Field {
+                world,
+                tree: EnemyKdTree(KdTree::build_by_ordered_float(collisions)),
+                near,
+                weak,
+                elite,
+                cluster,
+            }
+        }
+
+        fn select(
+            &mut self,
+            strategy: TargetingStrategy,
+            count: usize,
+            exclude: &HashSet<Entity>,
+        ) -> Vec<Entity> {
+            let mut state: SystemState<TargetQuery> = SystemState::new(&mut self.world);
+            let enemy_query = state.get(&self.world);
+            select_targets(
+                strategy,
+                &self.tree,
+                &enemy_query,
+                Vec2::ZERO,
+                RANGE,
+                count,
+                exclude,
+                &mut StdRng::seed_from_u64(7),
+            )
+        }
+    }
+
+    #[test]
+    fn nearest_and_farthest_sort_by_distance() {
+        let mut field = Field::new();
+        let none = HashSet::new();
+
+        assert_eq!(
+            field.select(TargetingStrategy::Nearest, 3, &none),
+            vec![field.near, field.weak, field.elite]
+        );
+        assert_eq!(
+            field.select(TargetingStrategy::Farthest, 2, &none),
+            vec![field.cluster[2], field.cluster[1]]
+        );
+    }
+
+    #[test]
+    fn health_orders_break_ties_by_distance() {
+        let mut field = Field::new();
+        let none = HashSet::new();
+
+        assert_eq!(
+            field.select(TargetingStrategy::LowestHealth, 2, &none),
+            vec![field.weak, field.cluster[0]]
+        );
+        assert_eq!(
+            field.select(TargetingStrategy::HighestHealth, 2, &none),
+            vec![field.elite, field.near]
+        );
+    }
+
+    #[test]
+    fn random_picks_from_every_live_enemy_in_range() {
+        let mut field = Field::new();
+
+        let picked: HashSet<Entity> = field
+            .select(TargetingStrategy::Random, 10, &HashSet::new())
+            .into_iter()
+            .collect();
+
+        let expected: HashSet<Entity> = [field.near, field.weak, field.elite]
+            .into_iter()
+            .chain(field.cluster)
+            .collect();
+        assert_eq!(picked, expected);
+    }
+
+    #[test]
+    fn densest_cluster_goes_for_the_pack() {
+        let mut field = Field::new();
+
+        assert_eq!(
+            field.select(TargetingStrategy::DensestCluster, 3, &HashSet::new()),
+            field.cluster.to_vec()
+        );
+    }
+
+    #[test]
+    fn elite_first_ranks_before_distance() {
+        let mut field = Field::new();
+
+        assert_eq!(
+            field.select(TargetingStrategy::EliteFirst, 2, &HashSet::new()),
+            vec![field.elite, field.near]
+        );
+    }
+
+    #[test]
+    fn excluded_enemies_are_left_for_others() {
+        let mut field = Field::new();
+
+        let first = field.select(TargetingStrategy::Nearest, 2, &HashSet::new());
+        let claimed: HashSet<Entity> = first.iter().copied().collect();
+        let second = field.select(TargetingStrategy::Nearest, 2, &claimed);
+
+        assert_eq!(first, vec![field.near, field.weak]);
+        assert_eq!(second, vec![field.elite, field.cluster[0]]);
+    }
+}
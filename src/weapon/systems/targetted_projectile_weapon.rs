@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use bevy::platform::collections::{HashMap, HashSet};
+
+use crate::enemy::*;
+use crate::prelude::*;
+use crate::weapon::systems::targeting::{TargetQuery, select_targets};
+use crate::weapon::*;
+
+const PROJECTILE_RADIUS: f32 = 8.0;
+const HOMING_TURN_RATE: f32 = 6.0;
+/// Roughly a screen away, enemies further out than this are never picked.
+const TARGETING_RANGE: f32 = 800.0;
+
+/// Angle the projectile in target `slot` veers off its target by. Projectiles sharing a
+/// target fan out around it, alternating sides and widening by `spread` each pair.
+fn volley_offset(slot: usize, targets: usize, spread: f32) -> f32 {
+    let share = slot / targets;
+    let side = if share % 2 == 0 { 1.0 } else { -1.0 };
+    share.div_ceil(2) as f32 * spread * side
+}
+
+#[add_system(schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn fire_targetted_projectile_weapons(
+    mut commands: Commands,
+    time: Res<Time>,
+    tree: Res<EnemyKdTree>,
+    mut rng: ResMut<WeaponRng>,
+    mut weapon_query: Query<(
+        Entity,
+        &mut TargettedProjectileWeapon,
+        &TargetingStrategy,
+        &GlobalTransform,
+    )>,
+    projectile_query: Query<&Projectile>,
+    enemy_query: TargetQuery,
+) {
+    // Enemies already chased by homing projectiles, per weapon, so new shots spread out
+    let mut claimed: HashMap<Entity, HashSet<Entity>> = HashMap::new();
+    for projectile in projectile_query.iter() {
+        if let Some(target) = projectile.homing.and_then(|homing| homing.target) {
+            claimed.entry(projectile.weapon).or_default().insert(target);
+        }
+    }
+
+    for (weapon_entity, mut weapon, strategy, weapon_global) in weapon_query.iter_mut() {
+        let period = Duration::from_secs_f32(1.0 / weapon.attack_rate.max(0.01));
+        if weapon.attack_timer.duration() != period {
+            weapon.attack_timer.set_duration(period);
+        }
+
+        weapon.attack_timer.tick(time.delta());
+        if !weapon.attack_timer.just_finished() {
+            continue;
+        }
+
+        let origin = weapon_global.translation();
+        let weapon_claims = claimed.remove(&weapon_entity).unwrap_or_default();
+        let mut targets = select_targets(
+            *strategy,
+            &tree,
+            &enemy_query,
+            origin.truncate(),
+            TARGETING_RANGE,
+            weapon.max_targets,
+            &weapon_claims,
+            &mut rng.0,
+        );
+        // Rather double up on a target than hold fire when everything is claimed
+        if targets.is_empty() && !weapon_claims.is_empty() {
+            targets = select_targets(
+                *strategy,
+                &tree,
+                &enemy_query,
+                origin.truncate(),
+                TARGETING_RANGE,
+                weapon.max_targets,
+                &HashSet::new(),
+                &mut rng.0,
+            );
+        }
+        if targets.is_empty() {
+            continue;
+        }
+
+        // One projectile per target slot, the extras share a target when there are fewer
+        let spread = weapon.spread_angle_deg.to_radians();
+        for i in 0..weapon.max_targets {
+            let target = targets[i % targets.len()];
+            let Ok((target_transform, ..)) = enemy_query.get(target) else {
+                continue;
+            };
+
+            let offset = volley_offset(i, targets.len(), spread);
+            let direction = Vec2::from_angle(offset).rotate(
+                (target_transform.translation() - origin)
+                    .truncate()
+                    .normalize_or(Vec2::X),
+            );
+
+            commands.spawn(
+                Projectile {
+                    weapon: weapon_entity,
+                    damage: weapon.damage,
+                    speed: weapon.projectile_speed,
+                    direction,
+                    radius: PROJECTILE_RADIUS,
+                    lifetime: Timer::from_seconds(weapon.projectile_lifetime, TimerMode::Once),
+                    homing: weapon.homing.then_some(Homing {
+                        target: Some(target),
+                        turn_rate: HOMING_TURN_RATE,
+                    }),
+                }
+                .bundle(weapon.projectile_image.clone(), origin),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_projectile_per_target_flies_straight() {
+        for slot in 0..3 {
+            assert_eq!(volley_offset(slot, 3, 10.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn projectiles_sharing_a_target_fan_out_either_side() {
+        let offsets: Vec<f32> = (0..7).map(|slot| volley_offset(slot, 2, 10.0)).collect();
+
+        assert_eq!(offsets, vec![0.0, 0.0, -10.0, -10.0, 10.0, 10.0, -20.0]);
+    }
+}
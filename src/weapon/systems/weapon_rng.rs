@@ -0,0 +1,8 @@
+use crate::prelude::*;
+use crate::weapon::*;
+use crate::world::WorldGeneratationConfig;
+
+#[add_system(schedule = OnEnter(GameState::InGame), plugin = WeaponPlugin)]
+fn reseed_weapon_rng(mut rng: ResMut<WeaponRng>, config: Res<WorldGeneratationConfig>) {
+    rng.reseed(config.seed as u64);
+}
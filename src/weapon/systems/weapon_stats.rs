@@ -0,0 +1,151 @@
+use crate::items::StatModifiers;
+use crate::prelude::*;
+use crate::weapon::*;
+
+/// Re-derives stats from the definition whenever the weapon levels up or its owner's
+/// modifiers change, never from the weapon's current stats.
+#[add_system(generics = <OrbitWeapon>, schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+#[add_system(generics = <OrbitAndLungeWeapon>, schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+#[add_system(generics = <DirectionalMeleeWeapon>, schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+#[add_system(generics = <TargettedProjectileWeapon>, schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+#[add_system(generics = <RandomProjectileWeapon>, schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+#[add_system(generics = <AoeAroundPlayerWeapon>, schedule = Update, plugin = WeaponPlugin, run_if = in_state(InGameState::Playing))]
+fn refresh_weapon_stats<W: WeaponComponent>(
+    definitions: WeaponDefinitions,
+    owner_query: Query<Ref<StatModifiers>>,
+    mut query: Query<(Ref<WeaponLevel>, &ChildOf, &mut W)>,
+) {
+    for (level, child_of, mut weapon) in query.iter_mut() {
+        let modifiers = owner_query.get(child_of.parent()).ok();
+        if !level.is_changed() && !modifiers.as_ref().is_some_and(|m| m.is_changed()) {
+            continue;
+        }
+
+        if let Some(stats) = definitions
+            .final_stats(W::Stats::KIND, level.0, modifiers.as_deref())
+            .and_then(W::Stats::from_weapon_stats)
+        {
+            weapon.apply_stats(&stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::systems::recompute_stat_modifiers;
+    use crate::items::{ItemAssets, PassiveItem, PassiveItemCatalog, PassiveItems};
+
+    const LEVEL: u32 = 4;
+
+    fn app_with_catalogs() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<WeaponCatalog>()
+            .init_asset::<PassiveItemCatalog>()
+            .add_systems(
+                Update,
+                (
+                    recompute_stat_modifiers,
+                    refresh_weapon_stats::<OrbitWeapon>,
+                )
+                    .chain(),
+            );
+
+        let weapons: WeaponCatalog =
+            ron::from_str(include_str!("../../../assets/data/weapons.ron")).unwrap();
+        let catalog = app
+            .world_mut()
+            .resource_mut::<Assets<WeaponCatalog>>()
+            .add(weapons);
+        app.insert_resource(WeaponAssets { catalog });
+
+        let items: PassiveItemCatalog =
+            ron::from_str(include_str!("../../../assets/data/items.ron")).unwrap();
+        let catalog = app
+            .world_mut()
+            .resource_mut::<Assets<PassiveItemCatalog>>()
+            .add(items);
+        app.insert_resource(ItemAssets { catalog });
+
+        app
+    }
+
+    fn base_stats(app: &App) -> OrbitStats {
+        let assets = app.world().resource::<WeaponAssets>();
+        let catalog = app
+            .world()
+            .resource::<Assets<WeaponCatalog>>()
+            .get(&assets.catalog)
+            .unwrap();
+        let definition = catalog
+            .weapons
+            .iter()
+            .find(|definition| definition.kind == WeaponKind::Orbit)
+            .unwrap();
+
+        let WeaponStats::Orbit(stats) = definition.stats_at(LEVEL) else {
+            panic!("orbit definition has the wrong stats");
+        };
+        stats
+    }
+
+    #[test]
+    fn removing_a_passive_restores_the_level_stats() {
+        let mut app = app_with_catalogs();
+        let base = base_stats(&app);
+
+        let weapon = app
+            .world_mut()
+            .spawn((
+                WeaponLevel(LEVEL),
+                OrbitWeapon {
+                    orbit_speed: 0.0,
+                    orbit_radius: 0.0,
+                    orbit_direction: OrbitDirection::CounterClockwise,
+                    satellite_count: 0,
+                    satellite_image: Handle::default(),
+                    contact_damage: 0.0,
+                    contact_cooldown_per_entity: 0.0,
+                },
+            ))
+            .id();
+        let owner = app
+            .world_mut()
+            .spawn((PassiveItems::default(), StatModifiers::default()))
+            .add_child(weapon)
+            .id();
+
+        app.world_mut().get_mut::<PassiveItems>(owner).unwrap().0 = vec![
+            (PassiveItem::Might, 3),
+            (PassiveItem::Area, 2),
+            (PassiveItem::Cooldown, 2),
+            (PassiveItem::Amount, 1),
+        ];
+        app.update();
+
+        let boosted = app.world().get::<OrbitWeapon>(weapon).unwrap();
+        assert!(boosted.contact_damage > base.contact_damage);
+        assert!(boosted.orbit_radius > base.orbit_radius);
+        assert!(boosted.satellite_count > base.satellite_count);
+        assert!(boosted.contact_cooldown_per_entity < base.contact_cooldown_per_entity);
+
+        app.world_mut()
+            .get_mut::<PassiveItems>(owner)
+            .unwrap()
+            .0
+            .clear();
+        app.update();
+
+        let restored = app.world().get::<OrbitWeapon>(weapon).unwrap();
+        assert_eq!(restored.orbit_speed, base.orbit_speed);
+        assert_eq!(restored.orbit_radius, base.orbit_radius);
+        assert_eq!(restored.orbit_direction, base.orbit_direction);
+        assert_eq!(restored.satellite_count, base.satellite_count);
+        assert_eq!(restored.contact_damage, base.contact_damage);
+        assert_eq!(
+            restored.contact_cooldown_per_entity,
+            base.contact_cooldown_per_entity
+        );
+    }
+}
@@ -20,5 +20,5 @@ impl Plugin for WorldPlugin {
 
 mod biome;
 pub use biome::*;
-mod systems;
+pub(crate) mod systems;
 mod world_gen_tester;
@@ -11,6 +11,12 @@ use crate::world::*;
 #[insert_resource(plugin = WorldPlugin)]
 pub struct ChunkManager(pub HashMap<(i32, i32), Entity>);
 
+#[add_system(schedule = OnExit(GameState::InGame), plugin = WorldPlugin)]
+pub(crate) fn reset_chunk_manager(mut chunks: ResMut<ChunkManager>) {
+    // The chunk entities themselves are state scoped
+    chunks.0.clear();
+}
+
 fn world_pos_to_chunk_coords(pos: Vec2, config: &WorldGeneratationConfig) -> (i32, i32) {
     let chunk_width_world = config.chunk_width as f32 * config.tile_size;
     let chunk_height_world = config.chunk_height as f32 * config.tile_size;
@@ -34,34 +40,36 @@ fn load_near_chunks(
     config: Res<WorldGeneratationConfig>,
     asset_server: Res<AssetServer>,
 ) {
-    let player_transform = match player_query.single() {
-        Ok(t) => t,
-        Err(_) => return,
-    };
-
-    let (player_chunk_x, player_chunk_y) =
-        world_pos_to_chunk_coords(player_transform.translation.truncate(), &config);
+    if player_query.is_empty() {
+        return;
+    }
 
     let tilemap_image = asset_server.load("textures/tiles/tilemap.png");
 
-    for dy in -config.load_radius..=config.load_radius {
-        for dx in -config.load_radius..=config.load_radius {
-            let chunk_coord = (player_chunk_x + dx, player_chunk_y + dy);
-
-            if chunks.0.contains_key(&chunk_coord) {
-                continue;
+    // Every local player keeps the chunks around them loaded
+    for player_transform in player_query.iter() {
+        let (player_chunk_x, player_chunk_y) =
+            world_pos_to_chunk_coords(player_transform.translation.truncate(), &config);
+
+        for dy in -config.load_radius..=config.load_radius {
+            for dx in -config.load_radius..=config.load_radius {
+                let chunk_coord = (player_chunk_x + dx, player_chunk_y + dy);
+
+                if chunks.0.contains_key(&chunk_coord) {
+                    continue;
+                }
+
+                let chunk_entity = generator.generate_chunk(
+                    &mut commands,
+                    chunk_coord.0,
+                    chunk_coord.1,
+                    &config,
+                    tilemap_image.clone(),
+                );
+
+                chunks.0.insert(chunk_coord, chunk_entity);
+                info!(" - Generated chunk at {:?}", chunk_coord);
             }
-
-            let chunk_entity = generator.generate_chunk(
-                &mut commands,
-                chunk_coord.0,
-                chunk_coord.1,
-                &config,
-                tilemap_image.clone(),
-            );
-
-            chunks.0.insert(chunk_coord, chunk_entity);
-            info!(" - Generated chunk at {:?}", chunk_coord);
         }
     }
 }
@@ -79,24 +87,28 @@ fn unload_far_chunks(
     mut chunks: ResMut<ChunkManager>,
     config: Res<WorldGeneratationConfig>,
 ) {
-    let player_transform = match player_query.single() {
-        Ok(t) => t,
-        Err(_) => return,
-    };
-
-    let (player_chunk_x, player_chunk_y) =
-        world_pos_to_chunk_coords(player_transform.translation.truncate(), &config);
+    let player_chunks: Vec<(i32, i32)> = player_query
+        .iter()
+        .map(|transform| world_pos_to_chunk_coords(transform.translation.truncate(), &config))
+        .collect();
+    if player_chunks.is_empty() {
+        return;
+    }
 
     let unload_distance = config.load_radius * 2;
 
-    // Find chunks to unload (too far away)
+    // Find chunks to unload (too far away from every player)
     let to_unload: Vec<(i32, i32)> = chunks
         .0
         .iter()
         .filter(|&(&chunk_coord, _)| {
             let (cx, cy) = chunk_coord;
-            (cx - player_chunk_x).abs() > unload_distance
-                || (cy - player_chunk_y).abs() > unload_distance
+            player_chunks
+                .iter()
+                .all(|&(player_chunk_x, player_chunk_y)| {
+                    (cx - player_chunk_x).abs() > unload_distance
+                        || (cy - player_chunk_y).abs() > unload_distance
+                })
         })
         .map(|(&chunk_coord, _)| chunk_coord)
         .collect();
@@ -119,6 +119,7 @@ fn generate_blank_chunk(
                     y,
                     texture: handle,
                 },
+                StateScoped(GameState::InGame),
                 Transform::from_translation(Vec3::new(
                     x as f32 * config.chunk_width as f32,
                     y as f32 * config.chunk_height as f32,
@@ -342,7 +342,7 @@ impl WorldGenerator {
         config: &WorldGeneratationConfig,
         image: Handle<Image>,
     ) -> Entity {
-        let tilemap = commands.spawn_empty().id();
+        let tilemap = commands.spawn(StateScoped(GameState::InGame)).id();
 
         let mut storage = TileStorage::empty(config.tilemap_size);
 